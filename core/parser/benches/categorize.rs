@@ -0,0 +1,31 @@
+//! Micro-benchmark for `categorize_item` (run with `cargo bench`)
+//!
+//! Uses `std::time::Instant` to avoid extra dependencies; compare numbers
+//! between runs on the same machine only.
+
+use std::hint::black_box;
+use std::time::Instant;
+use zenith_parser::categorize_item;
+
+const ITERATIONS: u32 = 100_000;
+
+fn bench(name: &str, title: &str, url: &str) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(categorize_item(black_box(title), black_box(url)));
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{:<24} {:>8.0} ns/iter",
+        name,
+        elapsed.as_nanos() as f64 / f64::from(ITERATIONS)
+    );
+}
+
+fn main() {
+    bench("movie_no_markers", "The Great Movie Title", "http://example.com/movie.mkv");
+    bench("movie_with_year", "The Great Movie (2022)", "http://example.com/movie.mkv");
+    bench("series_s01e01", "Great Show S01E05", "http://example.com/show.mkv");
+    bench("series_regex_fallback", "Great Show Episode 5", "http://example.com/show.mkv");
+    bench("live_stream", "News Channel HD", "http://example.com/live/news");
+}
@@ -1,14 +1,153 @@
-import init, { parse_m3u, version } from './pkg/zenith_parser.js';
+import init, {
+  build_tree_from_m3u,
+  categorize,
+  diff_playlists,
+  estimate_items,
+  parse_m3u,
+  parse_m3u_bytes,
+  parse_m3u_with_metrics,
+  parse_m3u_with_options,
+  title_markers,
+  version,
+  CategoryTree,
+} from './pkg/zenith_parser.js';
 
 export interface ParsedM3UItem {
   title: string;
   url: string;
+  original_url?: string;
   group: string;
   logo?: string;
   category: 'Movie' | 'Series' | 'LiveStream';
   year?: number;
   season?: number;
+  season_end?: number;
   episode?: number;
+  episode_end?: number;
+  is_finale: boolean;
+  is_complete: boolean;
+  audio_variant?: string;
+  quality?: string;
+  country?: string;
+  duration?: number;
+  original_title?: string;
+  source_title: string;
+  album?: string;
+  vlc_options?: Record<string, string>;
+  kodi_props?: Record<string, string>;
+  artwork?: Record<string, string>;
+  recordable?: boolean;
+  attributes?: Record<string, string>;
+}
+
+export interface CategorizationPolicy {
+  live_first?: boolean;
+  group_hints?: boolean;
+  unknown_extension_is_live?: boolean;
+  max_season?: number;
+  max_episode?: number;
+  classify_by_url_only?: boolean;
+  strip_provider_ids?: boolean;
+  strip_year_from_title?: boolean;
+}
+
+export interface ParserOptions {
+  strip_prefix?: string;
+  normalize_separators?: boolean;
+  policy?: CategorizationPolicy;
+  max_attribute_length?: number;
+  max_items?: number;
+  drop_separators?: boolean;
+  normalize_urls?: boolean;
+  encode_url_spaces?: boolean;
+  base_url?: string;
+  expand_season_ranges?: boolean;
+  strip_leading_emoji?: boolean;
+}
+
+export interface ParseWarning {
+  line: number;
+  message: string;
+}
+
+export interface ParseResult {
+  items: ParsedM3UItem[];
+  warnings: ParseWarning[];
+  truncated: boolean;
+  playlist_title?: string;
+  skipped_missing_url: number;
+}
+
+export interface ParseMetrics {
+  items: number;
+  bytes: number;
+  micros: number;
+}
+
+export interface MeasuredParse {
+  items: ParsedM3UItem[];
+  metrics: ParseMetrics;
+}
+
+export type ErrorCode =
+  | 'MissingHeader'
+  | 'EmptyFile'
+  | 'InvalidInput'
+  | 'UnsupportedEncoding'
+  | 'Io'
+  | 'Serialization';
+
+/** Error object thrown by the WASM functions */
+export interface ParseError {
+  code: ErrorCode;
+  message: string;
+  line?: number;
+}
+
+export interface ItemChange {
+  old: ParsedM3UItem;
+  new: ParsedM3UItem;
+}
+
+export interface PlaylistDiff {
+  added: ParsedM3UItem[];
+  removed: ParsedM3UItem[];
+  changed: ItemChange[];
+}
+
+export interface TreeOptions {
+  uncategorized_name?: string;
+  locale?: string;
+  group_case?: 'AsIs' | 'Title' | 'Lower' | 'Upper';
+  group_sort?: 'Name' | 'ByItemCountDesc';
+}
+
+/** Result of `titleMarkers()` */
+export interface TitleMarkers {
+  has_year: boolean;
+  has_episode: boolean;
+  has_quality: boolean;
+}
+
+/** Result of `CategoryTree.splitByCategory()` */
+export interface SplitPlaylists {
+  movies: string;
+  series: string;
+  live: string;
+}
+
+/** Group inside the `CategoryTree.toJson()` envelope */
+export interface TreeExportNode {
+  name: string;
+  items: ParsedM3UItem[];
+}
+
+/** Stable shape of `CategoryTree.toJson()` output */
+export interface TreeExport {
+  schema_version: number;
+  movies: TreeExportNode[];
+  series: TreeExportNode[];
+  live: TreeExportNode[];
 }
 
 let wasmInitialized = false;
@@ -40,8 +179,126 @@ export async function parseM3U(content: string): Promise<ParsedM3UItem[]> {
     return result as ParsedM3UItem[];
   } catch (error) {
     console.error('M3U parsing error:', error);
-    throw new Error(`Failed to parse M3U: ${error}`);
+    throw new Error(`Failed to parse M3U: ${(error as ParseError).message ?? error}`);
   }
 }
 
-export { version };
+/**
+ * Parse M3U content with parser options
+ * @param content M3U file content as string
+ * @param options Parser options (e.g. provider prefix to strip)
+ * @returns Array of parsed M3U items
+ */
+export async function parseM3UWithOptions(
+  content: string,
+  options: ParserOptions
+): Promise<ParsedM3UItem[]> {
+  if (!wasmInitialized) {
+    await initParser();
+  }
+
+  try {
+    const result = parse_m3u_with_options(content, options);
+    return result as ParsedM3UItem[];
+  } catch (error) {
+    console.error('M3U parsing error:', error);
+    throw new Error(`Failed to parse M3U: ${(error as ParseError).message ?? error}`);
+  }
+}
+
+/**
+ * Parse M3U bytes served in a legacy encoding
+ * @param bytes Raw playlist bytes
+ * @param encoding Encoding label (e.g. "windows-1254"), UTF-8 when omitted
+ * @returns Array of parsed M3U items
+ */
+export async function parseM3UBytes(
+  bytes: Uint8Array,
+  encoding?: string
+): Promise<ParsedM3UItem[]> {
+  if (!wasmInitialized) {
+    await initParser();
+  }
+
+  try {
+    const result = parse_m3u_bytes(bytes, encoding);
+    return result as ParsedM3UItem[];
+  } catch (error) {
+    console.error('M3U parsing error:', error);
+    throw new Error(`Failed to parse M3U: ${(error as ParseError).message ?? error}`);
+  }
+}
+
+/**
+ * Compare two parsed playlists by URL
+ * @param oldItems Previously parsed items
+ * @param newItems Freshly parsed items
+ * @returns Added, removed and changed (title/group/logo) items
+ */
+export function diffPlaylists(
+  oldItems: ParsedM3UItem[],
+  newItems: ParsedM3UItem[]
+): PlaylistDiff {
+  return diff_playlists(oldItems, newItems) as PlaylistDiff;
+}
+
+/**
+ * Predict how many items a playlist holds without parsing it
+ * @param content M3U file content as string
+ * @returns `#EXTINF` count, an upper bound of the parsed item count
+ */
+export async function estimateItems(content: string): Promise<number> {
+  if (!wasmInitialized) {
+    await initParser();
+  }
+
+  return estimate_items(content);
+}
+
+/**
+ * Parse M3U content and report item count, input size and parse time
+ * @param content M3U file content as string
+ * @returns Parsed items with metrics
+ */
+export async function parseM3UWithMetrics(content: string): Promise<MeasuredParse> {
+  if (!wasmInitialized) {
+    await initParser();
+  }
+
+  try {
+    return parse_m3u_with_metrics(content) as MeasuredParse;
+  } catch (error) {
+    console.error('M3U parsing error:', error);
+    throw new Error(`Failed to parse M3U: ${(error as ParseError).message ?? error}`);
+  }
+}
+
+/**
+ * Parse M3U content and build the category tree in a single WASM call
+ * @param content M3U file content as string
+ * @returns Category tree (call `free()` when done)
+ */
+export async function buildTreeFromM3U(content: string): Promise<CategoryTree> {
+  if (!wasmInitialized) {
+    await initParser();
+  }
+
+  try {
+    return build_tree_from_m3u(content);
+  } catch (error) {
+    console.error('M3U parsing error:', error);
+    throw new Error(`Failed to parse M3U: ${(error as ParseError).message ?? error}`);
+  }
+}
+
+/**
+ * Check a title for year, episode and quality markers without categorizing it
+ * @param title Raw title
+ * @returns Marker flags for UI badges
+ */
+export function titleMarkers(title: string): TitleMarkers {
+  return title_markers(title) as TitleMarkers;
+}
+
+export { categorize, version };
+export { CategoryTree };
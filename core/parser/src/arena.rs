@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+use std::mem;
+use std::sync::Arc;
+use crate::{M3UParser, ParseError, ParseResult, ParserOptions};
+
+/// Reusable parse buffers for feeds that are parsed repeatedly
+///
+/// Reuse contract: the result returned by `parse` borrows the arena and stays
+/// valid until the next `parse` call, which clears it and refills the same
+/// item buffer (its capacity is kept, so a refresh of a similarly sized feed
+/// doesn't regrow it). Group-titles are interned across parses, so groups
+/// that survive a refresh keep their allocation. Only those two buffers are
+/// reused; items themselves are allocated fresh. Call `into_result` to take
+/// ownership instead.
+#[derive(Debug, Default)]
+pub struct ParserArena {
+    result: ParseResult,
+    groups: HashSet<Arc<str>>,
+}
+
+impl ParserArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse `content`, reusing the buffers of the previous parse
+    ///
+    /// On error the previous result is kept as it was.
+    pub fn parse(
+        &mut self,
+        content: &str,
+        options: ParserOptions,
+    ) -> Result<&ParseResult, ParseError> {
+        let parser = M3UParser::with_options(content, options);
+        let result = parser.parse_into(&mut self.result.items, &mut self.groups)?;
+        self.result = ParseResult {
+            items: mem::take(&mut self.result.items),
+            ..result
+        };
+        Ok(&self.result)
+    }
+
+    /// Result of the last successful parse
+    pub fn result(&self) -> &ParseResult {
+        &self.result
+    }
+
+    /// Take the last result, leaving the arena empty
+    pub fn into_result(self) -> ParseResult {
+        self.result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_parse_keeps_previous_result() {
+        let content = "#EXTM3U\n#EXTINF:-1,Movie (2020)\nhttp://example.com/movie.mkv\n";
+        let mut arena = ParserArena::new();
+        arena.parse(content, ParserOptions::default()).unwrap();
+
+        assert!(arena.parse("not a playlist", ParserOptions::default()).is_err());
+        assert_eq!(arena.result().items.len(), 1);
+        assert_eq!(arena.result().items[0].title, "Movie");
+    }
+
+    #[test]
+    fn test_groups_interned_across_parses() {
+        let content =
+            "#EXTM3U\n#EXTINF:-1 group-title=\"Action\",Movie\nhttp://example.com/a.mkv\n";
+        let mut arena = ParserArena::new();
+        let first = arena.parse(content, ParserOptions::default()).unwrap().items[0].group.clone();
+
+        let second = &arena.parse(content, ParserOptions::default()).unwrap().items[0].group;
+        assert!(Arc::ptr_eq(&first, second));
+    }
+}
@@ -1,9 +1,31 @@
+use lazy_static::lazy_static;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use crate::episode_detector::detect_episode;
-use crate::year_detector::detect_year;
+use crate::detector::{DetectorPipeline, TitleContext};
+use crate::normalizer::url_filename;
+
+lazy_static! {
+    /// Built-in detector stages shared by `categorize_item`
+    static ref DEFAULT_PIPELINE: DetectorPipeline = DetectorPipeline::default();
+
+    /// Scene release tags that only appear on movie/episode files
+    /// Matches: BluRay, BRRip, WEB-DL, WEBRip, HDRip, DVDRip, REMUX, x264, HEVC, etc.
+    static ref RELEASE_TAG_PATTERN: Regex = Regex::new(
+        r"(?i)\b(blu-?ray|b[dr]rip|web-?dl|web-?rip|hdrip|dvdrip|remux|[xh]\.?26[45]|hevc)\b"
+    ).unwrap();
+
+    /// Numeric provider ID at the end of a title; 5+ digits so years never match
+    /// Matches: "Movie Name 4815162342", "Movie (2020) 123456"
+    static ref PROVIDER_ID_PATTERN: Regex = Regex::new(r"\s+\d{5,}\s*$").unwrap();
+
+    /// Turkish content type markers
+    /// Matches: (Dizi), [Film], ( dizi )
+    static ref TYPE_MARKER_PATTERN: Regex =
+        Regex::new(r"(?i)[(\[]\s*(dizi|film)\s*[)\]]").unwrap();
+}
 
 /// Content category (simplified - episode info moved to M3UItem)
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub enum Category {
     /// Live stream (no file extension in URL)
@@ -11,86 +33,427 @@ pub enum Category {
     /// TV Series episode
     Series,
     /// Movie or standalone content
+    #[default]
     Movie,
 }
 
+/// How sure the categorizer is about a `Movie` result
+///
+/// Movie is the fallback category, so a bare title on an unknown URL ends up
+/// there too; a release year and a scene release tag (`BluRay`, `WEB-DL`)
+/// each raise the confidence.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    /// Neither a year nor a release tag
+    #[default]
+    Low,
+    /// A year or a release tag
+    Medium,
+    /// Both a year and a release tag
+    High,
+}
+
 /// Result of item categorization with metadata
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct CategorizedItem {
     pub category: Category,
     pub cleaned_title: String,
     pub year: Option<u32>,
     pub season: Option<u32>,
+    /// Last season of a box-set range (`S01-S03`)
+    pub season_end: Option<u32>,
     pub episode: Option<u32>,
+    pub episode_end: Option<u32>,
+    pub is_finale: bool,
+    /// Whole-series bundle (`Complete Series`), no season/episode
+    pub is_complete: bool,
+    pub audio_variant: Option<String>,
+    pub quality: Option<String>,
+    /// ISO country code from a flag emoji or `(TR)`-style marker (live only)
+    pub country: Option<String>,
+    /// Set for `Movie` results only
+    pub confidence: Option<Confidence>,
 }
 
+/// Classification precedence used by `categorize_item_with_policy`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct CategorizationPolicy {
+    /// Check the URL for live streams before any title heuristics.
+    /// When false, a detected episode marker wins over an extension-less URL.
+    pub live_first: bool,
+    /// Let group-title keywords (`Series`, `VOD Movies`, `Live TV`, ...) decide
+    /// the category before URL/title heuristics
+    pub group_hints: bool,
+    /// Treat URLs without a file extension as live streams. Turn off for
+    /// providers whose VOD URLs are extension-less (`.../movie/678`)
+    pub unknown_extension_is_live: bool,
+    /// Episode matches with a higher season are rejected (`Model X S900`)
+    pub max_season: u32,
+    /// Episode matches with a higher episode number are rejected
+    pub max_episode: u32,
+    /// Decide the category from the URL alone (Xtream `/movie/`, `/series/`,
+    /// `/live/` paths, then the extension); title metadata is still extracted
+    pub classify_by_url_only: bool,
+    /// Drop a trailing run of 5+ digits (`Movie Name 4815162342`) before
+    /// detection; such provider IDs are never years or episode numbers
+    pub strip_provider_ids: bool,
+    /// Remove the detected year from the display title (`year` is set either way);
+    /// when false the title keeps it as written
+    pub strip_year_from_title: bool,
+}
+
+impl Default for CategorizationPolicy {
+    fn default() -> Self {
+        Self {
+            live_first: true,
+            group_hints: false,
+            unknown_extension_is_live: true,
+            max_season: 100,
+            max_episode: 2000,
+            classify_by_url_only: false,
+            strip_provider_ids: false,
+            strip_year_from_title: true,
+        }
+    }
+}
+
+/// Group-title keywords giving a strong category hint, checked in order
+const GROUP_KEYWORDS: [(Category, &[&str]); 3] = [
+    (Category::Series, &["series", "serie", "shows", "dizi", "diziler"]),
+    (Category::Movie, &["movies", "movie", "film", "films", "filmler", "peliculas"]),
+    (Category::LiveStream, &["live", "canlı", "channels"]),
+];
+
 /// Categorize an item based on title and URL, extracting all metadata
 ///
 /// This function:
 /// 1. Detects live streams by URL extension
-/// 2. Extracts year from title and cleans it
-/// 3. Detects series episodes (season/episode numbers)
-/// 4. Returns category with all extracted metadata
+/// 2. Strips bracketed quality tags ([HD], (FHD), ...)
+/// 3. Extracts audio variant (Latino, Subtitulado, ...) and cleans it
+/// 4. Extracts year from title and cleans it
+/// 5. Detects series episodes (season/episode numbers)
+/// 6. Returns category with all extracted metadata
 pub fn categorize_item(title: &str, url: &str) -> CategorizedItem {
-    // Check if it's a live stream (no file extension)
-    if is_live_stream(url) {
-        return CategorizedItem {
-            category: Category::LiveStream,
-            cleaned_title: title.to_string(),
-            year: None,
-            season: None,
-            episode: None,
-        };
+    categorize_item_with_policy(title, url, &CategorizationPolicy::default())
+}
+
+/// Categorize an item using a custom classification precedence
+///
+/// With the default policy this is identical to `categorize_item`.
+pub fn categorize_item_with_policy(
+    title: &str,
+    url: &str,
+    policy: &CategorizationPolicy,
+) -> CategorizedItem {
+    categorize_item_with_pipeline(title, url, policy, &DEFAULT_PIPELINE)
+}
+
+/// Built-in pipeline used when no custom one is configured
+pub(crate) fn default_pipeline() -> &'static DetectorPipeline {
+    &DEFAULT_PIPELINE
+}
+
+/// Categorize an item running a custom detector pipeline
+///
+/// The live-stream check and the final classification stay here; all title
+/// metadata extraction is delegated to the pipeline stages.
+pub fn categorize_item_with_pipeline(
+    title: &str,
+    url: &str,
+    policy: &CategorizationPolicy,
+    pipeline: &DetectorPipeline,
+) -> CategorizedItem {
+    categorize_item_in_group(title, url, "", policy, pipeline)
+}
+
+/// Categorize an item using its `#EXTINF` duration as a live/VOD tiebreaker
+///
+/// A positive duration marks an extension-less or HLS URL as VOD instead of
+/// live. `-1` (or no duration) keeps the URL heuristics: many playlists put
+/// `-1` on every entry, so it can't override a file extension.
+pub fn categorize_item_with_duration(
+    title: &str,
+    url: &str,
+    duration: Option<f64>,
+) -> CategorizedItem {
+    let policy = CategorizationPolicy::default();
+    categorize_entry(title, url, "", duration, &policy, &DEFAULT_PIPELINE)
+}
+
+/// Categorize an item taking its group-title into account
+///
+/// When `policy.group_hints` is set and the group contains a known keyword,
+/// that category wins; title metadata is still extracted by the pipeline.
+/// Otherwise identical to `categorize_item_with_pipeline`.
+pub fn categorize_item_in_group(
+    title: &str,
+    url: &str,
+    group: &str,
+    policy: &CategorizationPolicy,
+    pipeline: &DetectorPipeline,
+) -> CategorizedItem {
+    categorize_entry(title, url, group, None, policy, pipeline)
+}
+
+/// `categorize_item_in_group` with the entry's duration (see
+/// `categorize_item_with_duration`)
+pub(crate) fn categorize_entry(
+    title: &str,
+    url: &str,
+    group: &str,
+    duration: Option<f64>,
+    policy: &CategorizationPolicy,
+    pipeline: &DetectorPipeline,
+) -> CategorizedItem {
+    let title = if policy.strip_provider_ids {
+        PROVIDER_ID_PATTERN.replace(title, "")
+    } else {
+        title.into()
+    };
+
+    classify(&title, url, group, duration, policy, pipeline)
+}
+
+/// Category decision and metadata extraction through the pipeline
+fn classify(
+    title: &str,
+    url: &str,
+    group: &str,
+    duration: Option<f64>,
+    policy: &CategorizationPolicy,
+    pipeline: &DetectorPipeline,
+) -> CategorizedItem {
+    // An explicit `(Dizi)`/`(Film)` marker overrides every other signal
+    if let Some((category, cleaned)) = strip_type_marker(title) {
+        return hinted_item(category, run_pipeline(&cleaned, url, group, policy, pipeline));
     }
 
-    // Try to extract year from title
-    let (working_title, year) = if let Some(year_info) = detect_year(title) {
-        (year_info.cleaned_title, Some(year_info.year))
+    let hint = if policy.classify_by_url_only {
+        Some(url_category(url))
+    } else if policy.group_hints {
+        group_category_hint(group)
     } else {
-        (title.to_string(), None)
+        None
     };
+    match hint {
+        Some(Category::LiveStream) => return live_stream_item(title, url, group, pipeline),
+        Some(category) => {
+            return hinted_item(category, run_pipeline(title, url, group, policy, pipeline));
+        }
+        None => {}
+    }
+
+    // A known running time means VOD whatever the URL looks like
+    let has_runtime = duration.is_some_and(|seconds| seconds > 0.0);
+    let is_live = !has_runtime
+        && ((policy.unknown_extension_is_live && is_live_stream(url)) || is_live_playlist(url));
+
+    // Check if it's a live stream (no file extension, or HLS outside a VOD path)
+    if is_live && policy.live_first {
+        return live_stream_item(title, url, group, pipeline);
+    }
+
+    let ctx = run_pipeline(title, url, group, policy, pipeline);
 
     // Check if it's a series episode
-    if let Some(episode_info) = detect_episode(&working_title) {
+    if let Some(episode_info) = ctx.episode {
         return CategorizedItem {
             category: Category::Series,
             cleaned_title: episode_info.series_name,
-            year,
+            year: ctx.year,
             season: Some(episode_info.season),
             episode: Some(episode_info.episode),
+            episode_end: episode_info.episode_end,
+            is_finale: episode_info.is_finale,
+            audio_variant: ctx.audio_variant,
+            quality: ctx.quality,
+            ..Default::default()
+        };
+    }
+
+    // Whole-season entry, season range or complete bundle: series without episode
+    if ctx.season.is_some() || ctx.is_complete {
+        return CategorizedItem {
+            category: Category::Series,
+            cleaned_title: ctx.title,
+            year: ctx.year,
+            season: ctx.season,
+            season_end: ctx.season_end,
+            is_complete: ctx.is_complete,
+            audio_variant: ctx.audio_variant,
+            quality: ctx.quality,
+            ..Default::default()
         };
     }
 
+    // Live stream checked after series detection
+    if is_live {
+        return live_stream_item(title, url, group, pipeline);
+    }
+
     // Default to movie
     CategorizedItem {
         category: Category::Movie,
-        cleaned_title: working_title,
-        year,
-        season: None,
-        episode: None,
+        confidence: Some(movie_confidence(title, ctx.year)),
+        cleaned_title: ctx.title,
+        year: ctx.year,
+        audio_variant: ctx.audio_variant,
+        quality: ctx.quality,
+        ..Default::default()
     }
 }
 
-/// Detect if URL is a live stream (no file extension)
-fn is_live_stream(url: &str) -> bool {
-    // Find last slash
-    if let Some(last_slash) = url.rfind('/') {
-        let filename = &url[last_slash + 1..];
-
-        // Check if there's a dot after query params removal
-        let filename_without_query = if let Some(query_pos) = filename.find('?') {
-            &filename[..query_pos]
-        } else {
-            filename
-        };
+/// Confidence of a movie classification from its year and release tags
+fn movie_confidence(title: &str, year: Option<u32>) -> Confidence {
+    match (year.is_some(), RELEASE_TAG_PATTERN.is_match(title)) {
+        (true, true) => Confidence::High,
+        (false, false) => Confidence::Low,
+        _ => Confidence::Medium,
+    }
+}
+
+/// Run the detector stages, dropping episode matches above the policy caps
+fn run_pipeline(
+    title: &str,
+    url: &str,
+    group: &str,
+    policy: &CategorizationPolicy,
+    pipeline: &DetectorPipeline,
+) -> TitleContext {
+    let mut ctx = TitleContext::new(title, url);
+    ctx.group = group.to_string();
+    ctx.keep_year_in_title = !policy.strip_year_from_title;
+    pipeline.run(&mut ctx);
+    ctx.episode = ctx
+        .episode
+        .filter(|ep| ep.season <= policy.max_season && ep.episode <= policy.max_episode);
+    ctx.season = ctx.season.filter(|season| *season <= policy.max_season);
+    ctx.season_end = ctx.season_end.filter(|end| ctx.season.is_some() && *end <= policy.max_season);
+    ctx
+}
 
-        // No extension = live stream
-        !filename_without_query.contains('.')
+/// Path segments of Xtream Codes style URLs (`/movie/user/pass/1.mkv`)
+const URL_PATH_CATEGORIES: [(&str, Category); 5] = [
+    ("movie", Category::Movie),
+    ("movies", Category::Movie),
+    ("vod", Category::Movie),
+    ("series", Category::Series),
+    ("live", Category::LiveStream),
+];
+
+/// Category from URL structure only: a known path segment, otherwise live
+/// without a file extension (or for HLS) and movie with one
+fn url_category(url: &str) -> Category {
+    match url_path_category(url) {
+        Some(category) => category,
+        None if is_live_stream(url) || is_live_playlist(url) => Category::LiveStream,
+        None => Category::Movie,
+    }
+}
+
+/// Category of the first known path segment (`/vod/`, `/series/`, ...)
+fn url_path_category(url: &str) -> Option<Category> {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let path = path.split_once("://").map_or(path, |(_, rest)| rest);
+    path.split('/').skip(1).find_map(|segment| {
+        URL_PATH_CATEGORIES
+            .iter()
+            .find(|(name, _)| segment.eq_ignore_ascii_case(name))
+            .map(|(_, category)| category.clone())
+    })
+}
+
+/// Category of a `(Dizi)`/`(Film)` marker and the title without it
+fn strip_type_marker(title: &str) -> Option<(Category, String)> {
+    let captures = TYPE_MARKER_PATTERN.captures(title)?;
+    let marker = captures.get(0)?;
+    let category = if captures[1].eq_ignore_ascii_case("dizi") {
+        Category::Series
     } else {
-        false
+        Category::Movie
+    };
+    let cleaned = format!("{} {}", &title[..marker.start()], &title[marker.end()..]);
+    Some((category, cleaned.split_whitespace().collect::<Vec<&str>>().join(" ")))
+}
+
+/// Category implied by keywords in a group-title (`VOD Movies` -> Movie)
+fn group_category_hint(group: &str) -> Option<Category> {
+    let group = group.to_lowercase();
+    let words: Vec<&str> = group.split(|c: char| !c.is_alphanumeric()).collect();
+    GROUP_KEYWORDS
+        .iter()
+        .find(|(_, keywords)| words.iter().any(|word| keywords.contains(word)))
+        .map(|(category, _)| category.clone())
+}
+
+/// Build a Series/Movie result from pipeline output, forcing the category
+fn hinted_item(category: Category, ctx: TitleContext) -> CategorizedItem {
+    match (category, ctx.episode) {
+        (Category::Series, Some(episode_info)) => CategorizedItem {
+            category: Category::Series,
+            cleaned_title: episode_info.series_name,
+            year: ctx.year,
+            season: Some(episode_info.season),
+            episode: Some(episode_info.episode),
+            episode_end: episode_info.episode_end,
+            is_finale: episode_info.is_finale,
+            audio_variant: ctx.audio_variant,
+            quality: ctx.quality,
+            ..Default::default()
+        },
+        (category, _) => CategorizedItem {
+            season: ctx.season.filter(|_| category == Category::Series),
+            season_end: ctx.season_end.filter(|_| category == Category::Series),
+            is_complete: ctx.is_complete && category == Category::Series,
+            // The provider labelled the group (or URL path), no guessing involved
+            confidence: (category == Category::Movie).then_some(Confidence::High),
+            category,
+            cleaned_title: ctx.title,
+            year: ctx.year,
+            audio_variant: ctx.audio_variant,
+            quality: ctx.quality,
+            ..Default::default()
+        },
     }
 }
 
+/// Build a live stream result: the pipeline runs in live mode, so only tag
+/// cleanup stages (quality, country) touch the title and no VOD metadata is
+/// extracted
+fn live_stream_item(
+    title: &str,
+    url: &str,
+    group: &str,
+    pipeline: &DetectorPipeline,
+) -> CategorizedItem {
+    let mut ctx = TitleContext::new(title, url);
+    ctx.group = group.to_string();
+    ctx.live = true;
+    pipeline.run(&mut ctx);
+    CategorizedItem {
+        category: Category::LiveStream,
+        cleaned_title: ctx.title,
+        quality: ctx.quality,
+        country: ctx.country,
+        ..Default::default()
+    }
+}
+
+/// Detect if URL is a live stream (no file extension)
+fn is_live_stream(url: &str) -> bool {
+    url.contains('/') && !url_filename(url).contains('.')
+}
+
+/// HLS playlist (`.m3u8`) not under a VOD path: providers serving movies over
+/// HLS put them under `/vod/` or `/movie/`, live channels anywhere else
+fn is_live_playlist(url: &str) -> bool {
+    let is_hls = url_filename(url)
+        .rsplit_once('.')
+        .is_some_and(|(_, extension)| extension.eq_ignore_ascii_case("m3u8"));
+    is_hls && !matches!(url_path_category(url), Some(Category::Movie | Category::Series))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +508,424 @@ mod tests {
         assert_eq!(result.season, Some(1));
         assert_eq!(result.episode, Some(5));
     }
+
+    #[test]
+    fn test_audio_variants_share_title() {
+        let latino = categorize_item("Movie (Latino)", "http://example.com/movie.mkv");
+        let subbed = categorize_item("Movie (Subtitulado)", "http://example.com/movie-sub.mkv");
+        assert_eq!(latino.cleaned_title, "Movie");
+        assert_eq!(latino.cleaned_title, subbed.cleaned_title);
+        assert_eq!(latino.audio_variant, Some("Latino".to_string()));
+        assert_eq!(subbed.audio_variant, Some("Subtitulado".to_string()));
+    }
+
+    #[test]
+    fn test_policy_series_over_live() {
+        let title = "Show S01E02";
+        let url = "http://example.com/series/12345";
+
+        let default = categorize_item_with_policy(title, url, &CategorizationPolicy::default());
+        assert_eq!(default.category, Category::LiveStream);
+
+        let policy = CategorizationPolicy {
+            live_first: false,
+            ..Default::default()
+        };
+        let result = categorize_item_with_policy(title, url, &policy);
+        assert_eq!(result.category, Category::Series);
+        assert_eq!(result.cleaned_title, "Show");
+        assert_eq!(result.season, Some(1));
+        assert_eq!(result.episode, Some(2));
+
+        // Without an episode marker the URL still decides
+        let live = categorize_item_with_policy("News Channel", url, &policy);
+        assert_eq!(live.category, Category::LiveStream);
+    }
+
+    #[test]
+    fn test_weekly_year_episode() {
+        let result = categorize_item("Show 2024 E15", "http://example.com/show.mkv");
+        assert_eq!(result.category, Category::Series);
+        assert_eq!(result.cleaned_title, "Show");
+        assert_eq!(result.year, Some(2024));
+        assert_eq!(result.season, Some(1));
+        assert_eq!(result.episode, Some(15));
+    }
+
+    #[test]
+    fn test_bracketed_quality() {
+        let result = categorize_item("Channel [HD]", "http://example.com/live/channel");
+        assert_eq!(result.category, Category::LiveStream);
+        assert_eq!(result.cleaned_title, "Channel");
+        assert_eq!(result.quality, Some("HD".to_string()));
+
+        let result = categorize_item("Movie (FHD) (2020)", "http://example.com/movie.mkv");
+        assert_eq!(result.category, Category::Movie);
+        assert_eq!(result.cleaned_title, "Movie");
+        assert_eq!(result.year, Some(2020));
+        assert_eq!(result.quality, Some("FHD".to_string()));
+    }
+
+    #[test]
+    fn test_spaced_episode_marker() {
+        let result = categorize_item("Show S01 E01", "http://example.com/show.mkv");
+        assert_eq!(result.category, Category::Series);
+        assert_eq!(result.cleaned_title, "Show");
+        assert_eq!((result.season, result.episode), (Some(1), Some(1)));
+
+        let result = categorize_item("Show - S01 E02", "http://example.com/show.mkv");
+        assert_eq!(result.cleaned_title, "Show");
+        assert_eq!(result.episode, Some(2));
+    }
+
+    #[test]
+    fn test_strip_provider_ids() {
+        let policy = CategorizationPolicy {
+            strip_provider_ids: true,
+            ..Default::default()
+        };
+        let pipeline = default_pipeline();
+        let url = "http://example.com/movie.mkv";
+
+        let result = categorize_item_in_group("Movie Name 4815162342", url, "", &policy, pipeline);
+        assert_eq!(result.cleaned_title, "Movie Name");
+
+        let result = categorize_item_in_group("Movie 2020 4815162342", url, "", &policy, pipeline);
+        assert_eq!(result.cleaned_title, "Movie");
+        assert_eq!(result.year, Some(2020));
+
+        let result = categorize_item_in_group("Movie Name 2020", url, "", &policy, pipeline);
+        assert_eq!(result.year, Some(2020));
+
+        // Off by default
+        let result = categorize_item("Movie Name 4815162342", url);
+        assert_eq!(result.cleaned_title, "Movie Name 4815162342");
+    }
+
+    #[test]
+    fn test_country_marker() {
+        let result = categorize_item("🇹🇷 TRT 1 [HD]", "http://example.com/live/trt1");
+        assert_eq!(result.category, Category::LiveStream);
+        assert_eq!(result.cleaned_title, "TRT 1");
+        assert_eq!(result.country, Some("TR".to_string()));
+        assert_eq!(result.quality, Some("HD".to_string()));
+
+        let result = categorize_item("Kanal D (TR)", "http://example.com/live/kanald");
+        assert_eq!(result.cleaned_title, "Kanal D");
+        assert_eq!(result.country, Some("TR".to_string()));
+
+        // Kept on VOD titles
+        let result = categorize_item("The Office (US)", "http://example.com/office.mkv");
+        assert_eq!(result.cleaned_title, "The Office (US)");
+        assert_eq!(result.country, None);
+
+        // A pipeline without the country stage keeps the marker
+        let mut pipeline = DetectorPipeline::default();
+        pipeline.detectors.remove(1);
+        let policy = CategorizationPolicy::default();
+        let live = "http://example.com/live/kanald";
+        let result = categorize_item_with_pipeline("Kanal D (TR)", live, &policy, &pipeline);
+        assert_eq!(result.cleaned_title, "Kanal D (TR)");
+        assert_eq!(result.country, None);
+    }
+
+    #[test]
+    fn test_season_range() {
+        let result = categorize_item("Show S01-S03 Complete", "http://example.com/box.mkv");
+        assert_eq!(result.category, Category::Series);
+        assert_eq!(result.cleaned_title, "Show");
+        assert_eq!(result.season, Some(1));
+        assert_eq!(result.season_end, Some(3));
+        assert_eq!(result.episode, None);
+
+        let result = categorize_item("Show S01E03", "http://example.com/show.mkv");
+        assert_eq!(result.episode, Some(3));
+        assert_eq!(result.season_end, None);
+    }
+
+    #[test]
+    fn test_hls_under_vod_path() {
+        let result = categorize_item("Great Movie (2020)", "http://example.com/vod/x.m3u8");
+        assert_eq!(result.category, Category::Movie);
+        assert_eq!(result.year, Some(2020));
+        let result = categorize_item("Great Movie", "http://example.com/movie/1/x.M3U8?t=1");
+        assert_eq!(result.category, Category::Movie);
+
+        let result = categorize_item("News Channel", "http://example.com/hls/news.m3u8");
+        assert_eq!(result.category, Category::LiveStream);
+        let result = categorize_item("News Channel", "http://example.com/live/news.m3u8");
+        assert_eq!(result.category, Category::LiveStream);
+    }
+
+    #[test]
+    fn test_turkish_type_markers() {
+        let result = categorize_item("Yapım (Dizi)", "http://example.com/yapim.mkv");
+        assert_eq!(result.category, Category::Series);
+        assert_eq!(result.cleaned_title, "Yapım");
+
+        let result = categorize_item("Yapım (Film)", "http://example.com/yapim.mkv");
+        assert_eq!(result.category, Category::Movie);
+        assert_eq!(result.cleaned_title, "Yapım");
+        assert_eq!(result.confidence, Some(Confidence::High));
+
+        // Wins over the extension-less live guess and the episode marker
+        let result = categorize_item("Yapım [FILM] 2x03 (2021)", "http://example.com/live/42");
+        assert_eq!(result.category, Category::Movie);
+        assert_eq!(result.year, Some(2021));
+    }
+
+    #[test]
+    fn test_duration_tiebreaker() {
+        let url = "http://example.com/stream/12345";
+        let result = categorize_item_with_duration("Great Movie (2020)", url, Some(7200.0));
+        assert_eq!(result.category, Category::Movie);
+        assert_eq!(result.year, Some(2020));
+
+        let result = categorize_item_with_duration("News", url, Some(-1.0));
+        assert_eq!(result.category, Category::LiveStream);
+        assert_eq!(categorize_item_with_duration("News", url, None).category, Category::LiveStream);
+
+        // -1 doesn't override a file extension
+        let result = categorize_item_with_duration("Movie", "http://example.com/a.mkv", Some(-1.0));
+        assert_eq!(result.category, Category::Movie);
+    }
+
+    #[test]
+    fn test_classify_by_url_only() {
+        let policy = CategorizationPolicy {
+            classify_by_url_only: true,
+            ..Default::default()
+        };
+        let categorize = |title: &str, url: &str| categorize_item_with_policy(title, url, &policy);
+
+        let result = categorize("Show S01E01", "http://host:8080/movie/user/pass/1.mkv");
+        assert_eq!(result.category, Category::Movie);
+        assert_eq!(result.episode, None);
+
+        let result = categorize("Pilot (2008)", "http://host:8080/series/user/pass/2.mkv");
+        assert_eq!(result.category, Category::Series);
+        assert_eq!(result.year, Some(2008));
+
+        let result = categorize("Show S01E01", "http://host:8080/user/pass/3");
+        assert_eq!(result.category, Category::LiveStream);
+
+        // Default policy still trusts the title
+        assert_eq!(
+            categorize_item("Show S01E01", "http://host:8080/movie/user/pass/1.mkv").category,
+            Category::Series
+        );
+    }
+
+    #[test]
+    fn test_complete_series_marker() {
+        let result = categorize_item("Show Name Complete", "http://example.com/pack.mkv");
+        assert_eq!(result.category, Category::Series);
+        assert_eq!(result.cleaned_title, "Show Name");
+        assert!(result.is_complete);
+        assert_eq!(result.season, None);
+        assert_eq!(result.episode, None);
+
+        let result = categorize_item("Show S01-S03 Complete", "http://example.com/box.mkv");
+        assert!(result.is_complete);
+        assert_eq!(result.season_end, Some(3));
+
+        assert!(!categorize_item("Show S01E01", "http://example.com/show.mkv").is_complete);
+    }
+
+    #[test]
+    fn test_movie_confidence() {
+        let confident = categorize_item("Film 2020 BluRay", "http://example.com/film.mkv");
+        assert_eq!(confident.category, Category::Movie);
+        assert_eq!(confident.confidence, Some(Confidence::High));
+
+        let vague = categorize_item("Random Title", "http://example.com/random.mkv");
+        assert_eq!(vague.category, Category::Movie);
+        assert_eq!(vague.confidence, Some(Confidence::Low));
+        assert!(vague.confidence < confident.confidence);
+
+        let dated = categorize_item("Film (2020)", "http://example.com/film.mkv");
+        assert_eq!(dated.confidence, Some(Confidence::Medium));
+
+        let show = categorize_item("Show S01E01", "http://example.com/show.mkv");
+        assert_eq!(show.confidence, None);
+    }
+
+    #[test]
+    fn test_custom_noop_detector_in_pipeline() {
+        use crate::detector::Detector;
+
+        struct NoopDetector;
+        impl Detector for NoopDetector {
+            fn process(&self, _ctx: &mut TitleContext) {}
+        }
+
+        let mut pipeline = DetectorPipeline::default();
+        pipeline.detectors.insert(1, Box::new(NoopDetector));
+        assert_eq!(pipeline.detectors.len(), 6);
+
+        let policy = CategorizationPolicy::default();
+        for (title, url) in [
+            ("Show Name (2023) S01E05", "http://example.com/show.mkv"),
+            ("Movie (Latino) 2020", "http://example.com/movie.mkv"),
+            ("Live Channel", "http://example.com/channel1"),
+        ] {
+            assert_eq!(
+                categorize_item_with_pipeline(title, url, &policy, &pipeline),
+                categorize_item(title, url)
+            );
+        }
+    }
+
+    #[test]
+    fn test_pipeline_without_episode_stage() {
+        let mut pipeline = DetectorPipeline::default();
+        pipeline.detectors.pop();
+
+        let result = categorize_item_with_pipeline(
+            "Show S01E01",
+            "http://example.com/show.mkv",
+            &CategorizationPolicy::default(),
+            &pipeline,
+        );
+        assert_eq!(result.category, Category::Movie);
+        assert_eq!(result.cleaned_title, "Show S01E01");
+    }
+
+    #[test]
+    fn test_pipeline_without_quality_stage() {
+        let mut pipeline = DetectorPipeline::default();
+        pipeline.detectors.remove(0);
+        let policy = CategorizationPolicy::default();
+
+        let live = "http://example.com/live/1";
+        let result = categorize_item_with_pipeline("Channel [HD]", live, &policy, &pipeline);
+        assert_eq!(result.category, Category::LiveStream);
+        assert_eq!(result.cleaned_title, "Channel [HD]");
+        assert_eq!(result.quality, None);
+
+        let movie = "http://example.com/movie.mkv";
+        let result = categorize_item_with_pipeline("Movie (FHD)", movie, &policy, &pipeline);
+        assert_eq!(result.cleaned_title, "Movie (FHD)");
+        assert_eq!(result.quality, None);
+    }
+
+    #[test]
+    fn test_episode_from_url_filename() {
+        let result = categorize_item("Episode", "http://example.com/series/Show.S03E07.mkv");
+        assert_eq!(result.category, Category::Series);
+        assert_eq!(result.cleaned_title, "Show");
+        assert_eq!(result.season, Some(3));
+        assert_eq!(result.episode, Some(7));
+
+        let result = categorize_item("Pilot", "http://example.com/series/S01E01.mkv");
+        assert_eq!(result.category, Category::Series);
+        assert_eq!(result.cleaned_title, "Pilot");
+
+        let result = categorize_item("Episode", "http://example.com/movies/12345.mkv");
+        assert_eq!(result.category, Category::Movie);
+    }
+
+    #[test]
+    fn test_titles_without_markers() {
+        for title in ["Movie Title", "Şahane Film", "Episode Finale", "Season of the Witch"] {
+            let result = categorize_item(title, "http://example.com/movie.mkv");
+            assert_eq!(result.category, Category::Movie);
+            assert_eq!(result.cleaned_title, title);
+            assert_eq!(result.year, None);
+        }
+    }
+
+    #[test]
+    fn test_group_hints() {
+        let policy = CategorizationPolicy {
+            group_hints: true,
+            ..Default::default()
+        };
+        let pipeline = DetectorPipeline::default();
+
+        let series = categorize_item_in_group(
+            "Great Show Pilot", "http://example.com/a.mkv", "Series", &policy, &pipeline,
+        );
+        assert_eq!(series.category, Category::Series);
+        assert_eq!(series.cleaned_title, "Great Show Pilot");
+
+        let movie = categorize_item_in_group(
+            "Great Movie S01E01", "http://example.com/a.mkv", "VOD Movies", &policy, &pipeline,
+        );
+        assert_eq!(movie.category, Category::Movie);
+
+        let live = categorize_item_in_group(
+            "News (2024)", "http://example.com/a.mkv", "Live TV", &policy, &pipeline,
+        );
+        assert_eq!(live.category, Category::LiveStream);
+        assert_eq!(live.cleaned_title, "News (2024)");
+
+        // Unknown groups and the default policy fall back to the heuristics
+        let other = categorize_item_in_group(
+            "Great Show Pilot", "http://example.com/a.mkv", "Sports", &policy, &pipeline,
+        );
+        assert_eq!(other.category, Category::Movie);
+        let off = categorize_item_in_group(
+            "Great Show Pilot", "http://example.com/a.mkv", "Series",
+            &CategorizationPolicy::default(), &pipeline,
+        );
+        assert_eq!(off.category, Category::Movie);
+    }
+
+    #[test]
+    fn test_episode_sanity_cap() {
+        let result = categorize_item("Gadget S900", "http://example.com/gadget.mkv");
+        assert_eq!(result.category, Category::Movie);
+        assert_eq!(result.cleaned_title, "Gadget S900");
+
+        let policy = CategorizationPolicy {
+            max_season: 5,
+            ..Default::default()
+        };
+        let url = "http://example.com/show.mkv";
+        let capped = categorize_item_with_policy("Show S10E01", url, &policy);
+        assert_eq!(capped.category, Category::Movie);
+        assert_eq!(capped.season, None);
+        let kept = categorize_item_with_policy("Show S05E01", url, &policy);
+        assert_eq!(kept.category, Category::Series);
+    }
+
+    #[test]
+    fn test_season_only_entry() {
+        let result = categorize_item("Show - Season 3", "http://example.com/show.mkv");
+        assert_eq!(result.category, Category::Series);
+        assert_eq!(result.cleaned_title, "Show");
+        assert_eq!(result.season, Some(3));
+        assert_eq!(result.episode, None);
+    }
+
+    #[test]
+    fn test_unknown_extension_not_live() {
+        let url = "http://example.com/movie/678";
+        assert_eq!(categorize_item("Great Movie (2020)", url).category, Category::LiveStream);
+
+        let policy = CategorizationPolicy {
+            unknown_extension_is_live: false,
+            ..Default::default()
+        };
+        let result = categorize_item_with_policy("Great Movie (2020)", url, &policy);
+        assert_eq!(result.category, Category::Movie);
+        assert_eq!(result.cleaned_title, "Great Movie");
+        assert_eq!(result.year, Some(2020));
+    }
+
+    #[test]
+    fn test_season_implied_by_group() {
+        let pipeline = DetectorPipeline::default();
+        let policy = CategorizationPolicy::default();
+        let url = "http://example.com/ep5.mkv";
+
+        let result = categorize_item_in_group("Episode 5", url, "Season 2", &policy, &pipeline);
+        assert_eq!(result.category, Category::Series);
+        assert_eq!(result.season, Some(2));
+        assert_eq!(result.episode, Some(5));
+
+        let result = categorize_item_with_policy("Episode 5", url, &policy);
+        assert_eq!(result.season, Some(1));
+    }
 }
@@ -0,0 +1,1902 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use crate::collation::compare_names;
+use crate::{to_m3u, CategorizationPolicy, Category, M3UItem, M3UParser, ParseError};
+
+/// Default group name for items without a group-title
+const UNCATEGORIZED: &str = "Uncategorized";
+
+/// Version of the `CategoryTree::to_json` envelope; bump on breaking shape changes
+pub const TREE_SCHEMA_VERSION: u32 = 1;
+
+/// Options applied when building a `CategoryTree`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TreeOptions {
+    /// Group name for items without a group-title (e.g. "Kategorisiz")
+    pub uncategorized_name: String,
+    /// Collation for group and title sorting (`tr`); `None` compares lowercased text
+    pub locale: Option<String>,
+    /// Casing applied to group names so `action` and `ACTION` share a node;
+    /// items keep the raw name in `group`
+    pub group_case: GroupCase,
+    /// Order of non-sticky groups in the category queries (`get_movies`, ...)
+    pub group_sort: GroupSort,
+}
+
+impl Default for TreeOptions {
+    fn default() -> Self {
+        Self {
+            uncategorized_name: UNCATEGORIZED.to_string(),
+            locale: None,
+            group_case: GroupCase::default(),
+            group_sort: GroupSort::default(),
+        }
+    }
+}
+
+/// Order of the groups returned by the category queries, after sticky groups
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GroupSort {
+    /// Alphabetical (collated by `TreeOptions::locale`)
+    #[default]
+    Name,
+    /// Biggest groups first, ties by name
+    ByItemCountDesc,
+}
+
+/// Group name canonicalization used by `CategoryTree::build_with_options`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GroupCase {
+    /// Keep the group-title as written
+    #[default]
+    AsIs,
+    /// `action movies` -> `Action Movies`
+    Title,
+    Lower,
+    Upper,
+}
+
+impl GroupCase {
+    /// Canonical spelling of `name` in this case
+    pub fn apply(self, name: &str) -> String {
+        match self {
+            GroupCase::AsIs => name.to_string(),
+            GroupCase::Lower => name.to_lowercase(),
+            GroupCase::Upper => name.to_uppercase(),
+            GroupCase::Title => {
+                let mut titled = String::with_capacity(name.len());
+                let mut word_start = true;
+                for c in name.chars() {
+                    if word_start {
+                        titled.extend(c.to_uppercase());
+                    } else {
+                        titled.extend(c.to_lowercase());
+                    }
+                    // Apostrophes stay inside the word: `kid's` -> `Kid's`
+                    word_start = c.is_whitespace() || "-|:–—·/,".contains(c);
+                }
+                titled
+            }
+        }
+    }
+}
+
+/// Group node holding all items that share a group-title within one type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryNode {
+    pub name: String,
+    pub items: Vec<M3UItem>,
+    /// Playlist position of each item (parallel to `items`), assigned by
+    /// `CategoryTree::build` and only used for ordering inside the tree
+    #[serde(skip)]
+    positions: Vec<usize>,
+}
+
+impl CategoryNode {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            items: Vec::new(),
+            positions: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, item: M3UItem, position: usize) {
+        self.items.push(item);
+        self.positions.push(position);
+    }
+
+    /// Items with their playlist position (item index when none was recorded)
+    fn entries(&self) -> impl Iterator<Item = (&M3UItem, usize)> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (item, self.positions.get(index).copied().unwrap_or(index)))
+    }
+
+    /// Serialize just this group's items into an M3U playlist
+    pub fn to_m3u(&self) -> String {
+        to_m3u(self.items.iter())
+    }
+}
+
+/// Lightweight node description returned to the UI (items fetched separately)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CategorySummary {
+    pub name: String,
+    pub count: usize,
+    pub sticky: bool,
+}
+
+/// Group summaries cut to a maximum count, see `CategoryTree::get_groups_capped`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CappedGroups {
+    pub groups: Vec<CategorySummary>,
+    /// Number of visible groups before the cap
+    pub total: usize,
+    /// Groups beyond the cap were left out
+    pub has_more: bool,
+}
+
+/// One M3U playlist per content type, see `CategoryTree::split_by_category`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SplitPlaylists {
+    pub movies: String,
+    pub series: String,
+    pub live: String,
+}
+
+/// Movies and series bucketed by year, see `CategoryTree::items_by_year`
+///
+/// Serializes as `{ "2021": [...], "2022": [...], "unknown": [...] }`.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct ItemsByYear {
+    #[serde(flatten)]
+    pub years: BTreeMap<u32, Vec<M3UItem>>,
+    /// Items without a detected year
+    pub unknown: Vec<M3UItem>,
+}
+
+/// Group summaries of all three content types, serialized in one pass
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AllCategories {
+    pub movies: Vec<CategorySummary>,
+    pub series: Vec<CategorySummary>,
+    pub live_streams: Vec<CategorySummary>,
+}
+
+/// Stable JSON envelope written by `CategoryTree::to_json`
+#[derive(Serialize)]
+struct TreeExport<'a> {
+    schema_version: u32,
+    movies: Vec<ExportNode<'a>>,
+    series: Vec<ExportNode<'a>>,
+    live: Vec<ExportNode<'a>>,
+}
+
+/// One group inside the JSON envelope
+#[derive(Serialize)]
+struct ExportNode<'a> {
+    name: &'a str,
+    items: Vec<ExportItem<'a>>,
+}
+
+impl<'a> ExportNode<'a> {
+    fn list(nodes: &'a [CategoryNode]) -> Vec<Self> {
+        nodes
+            .iter()
+            .map(|node| ExportNode {
+                name: &node.name,
+                items: node.items.iter().map(ExportItem::from).collect(),
+            })
+            .collect()
+    }
+}
+
+/// Item fields of schema version 1, decoupled from `M3UItem` so new item
+/// fields don't change the stored shape; adding or renaming one here needs a
+/// `TREE_SCHEMA_VERSION` bump
+#[derive(Serialize)]
+struct ExportItem<'a> {
+    title: &'a str,
+    url: &'a str,
+    original_url: Option<&'a str>,
+    group: &'a str,
+    logo: Option<&'a str>,
+    category: &'a Category,
+    year: Option<u32>,
+    season: Option<u32>,
+    episode: Option<u32>,
+    episode_end: Option<u32>,
+    is_finale: bool,
+    audio_variant: Option<&'a str>,
+    source_title: &'a str,
+    album: Option<&'a str>,
+    vlc_options: &'a HashMap<String, String>,
+    kodi_props: &'a HashMap<String, String>,
+    artwork: &'a HashMap<String, String>,
+    attributes: &'a HashMap<String, String>,
+}
+
+impl<'a> From<&'a M3UItem> for ExportItem<'a> {
+    fn from(item: &'a M3UItem) -> Self {
+        Self {
+            title: &item.title,
+            url: &item.url,
+            original_url: item.original_url.as_deref(),
+            group: &item.group,
+            logo: item.logo.as_deref(),
+            category: &item.category,
+            year: item.year,
+            season: item.season,
+            episode: item.episode,
+            episode_end: item.episode_end,
+            is_finale: item.is_finale,
+            audio_variant: item.audio_variant.as_deref(),
+            source_title: &item.source_title,
+            album: item.album.as_deref(),
+            vlc_options: &item.vlc_options,
+            kodi_props: &item.kodi_props,
+            artwork: &item.artwork,
+            attributes: &item.attributes,
+        }
+    }
+}
+
+/// Per-user item preferences applied when listing items
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserItemPrefs {
+    /// URLs of favorited items (pinned to the top)
+    pub favorites: Vec<String>,
+    /// URLs of hidden items
+    pub hidden: Vec<String>,
+    /// Keep hidden items in the result, flagged with `hidden: true`
+    pub include_hidden: bool,
+    /// Playback state per item URL
+    pub playback: HashMap<String, PlaybackState>,
+    /// "Continue watching" mode: in-progress items go to the top
+    pub continue_watching: bool,
+    /// Order of the remaining (non-pinned) items
+    pub sort: ItemSort,
+    /// Pin at most this many favorites (earliest in `favorites` first); the
+    /// others are sorted with the rest. `None` pins all of them
+    pub favorite_pin_limit: Option<usize>,
+}
+
+/// Item order used by `CategoryTree::get_items` after pinned items
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemSort {
+    /// Title, then season and episode
+    #[default]
+    Title,
+    /// Order of the source playlist
+    InsertionOrder,
+}
+
+/// Saved playback state of one item
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlaybackState {
+    pub position_secs: Option<u32>,
+    pub watched: Option<bool>,
+}
+
+impl PlaybackState {
+    /// Started but not finished
+    pub fn in_progress(&self) -> bool {
+        self.position_secs.is_some_and(|position| position > 0) && self.watched != Some(true)
+    }
+}
+
+/// Item as returned by `get_items`, with per-user flags
+#[derive(Debug, Clone, Serialize)]
+pub struct ItemView {
+    #[serde(flatten)]
+    pub item: M3UItem,
+    pub hidden: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_secs: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watched: Option<bool>,
+}
+
+/// Group whose name matched a search query
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroupMatch {
+    pub category: Category,
+    pub name: String,
+    pub count: usize,
+}
+
+/// Result of `CategoryTree::search`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SearchResults {
+    pub groups: Vec<GroupMatch>,
+    pub items: Vec<M3UItem>,
+}
+
+/// Group-based hierarchy: Type (Movies/Series/Live) -> Group -> Items
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default)]
+pub struct CategoryTree {
+    movies: Vec<CategoryNode>,
+    series: Vec<CategoryNode>,
+    live_streams: Vec<CategoryNode>,
+    /// URL -> (content type, node index, item index); first occurrence wins
+    url_index: HashMap<String, (Category, usize, usize)>,
+    options: TreeOptions,
+}
+
+impl CategoryTree {
+    /// Build tree from parsed items, keeping groups in encounter order
+    pub fn build(items: Vec<M3UItem>) -> Self {
+        Self::build_with_options(items, TreeOptions::default())
+    }
+
+    /// `build` with custom options (e.g. a localized uncategorized group name)
+    pub fn build_with_options(items: Vec<M3UItem>, options: TreeOptions) -> Self {
+        let mut tree = Self {
+            options,
+            ..Self::default()
+        };
+        let mut movie_index: HashMap<String, usize> = HashMap::new();
+        let mut series_index: HashMap<String, usize> = HashMap::new();
+        let mut live_index: HashMap<String, usize> = HashMap::new();
+
+        for (position, item) in items.into_iter().enumerate() {
+            let (nodes, index) = match item.category {
+                Category::Movie => (&mut tree.movies, &mut movie_index),
+                Category::Series => (&mut tree.series, &mut series_index),
+                Category::LiveStream => (&mut tree.live_streams, &mut live_index),
+            };
+
+            let group = if item.group.is_empty() {
+                tree.options.uncategorized_name.clone()
+            } else {
+                tree.options.group_case.apply(&item.group)
+            };
+
+            let node = *index.entry(group).or_insert_with_key(|name| {
+                nodes.push(CategoryNode::new(name.clone()));
+                nodes.len() - 1
+            });
+
+            nodes[node].push(item, position);
+        }
+
+        tree.rebuild_indexes();
+        tree
+    }
+
+    /// Merge groups with fewer than `min_items` items into the uncategorized
+    /// node of their content type (created at the end if missing)
+    ///
+    /// `min_items` of 0 or 1 keeps every group.
+    pub fn collapse_small_groups(&mut self, min_items: usize) {
+        let uncategorized = &self.options.uncategorized_name;
+        for nodes in [&mut self.movies, &mut self.series, &mut self.live_streams] {
+            let mut merged = CategoryNode::new(uncategorized.clone());
+            nodes.retain_mut(|node| {
+                if node.name == *uncategorized || node.items.len() >= min_items {
+                    return true;
+                }
+                for (item, position) in node.items.drain(..).zip(node.positions.drain(..)) {
+                    merged.push(item, position);
+                }
+                false
+            });
+            if merged.items.is_empty() {
+                continue;
+            }
+
+            match nodes.iter_mut().find(|node| node.name == *uncategorized) {
+                Some(node) => {
+                    node.items.append(&mut merged.items);
+                    node.positions.append(&mut merged.positions);
+                }
+                None => nodes.push(merged),
+            }
+        }
+
+        self.rebuild_indexes();
+    }
+
+    /// Change the group order of later category queries without rebuilding
+    pub fn set_group_sort(&mut self, sort: GroupSort) {
+        self.options.group_sort = sort;
+    }
+
+    fn locale(&self) -> Option<&str> {
+        self.options.locale.as_deref()
+    }
+
+    /// Rebuild the lookup indexes (URL -> location) from the current nodes
+    ///
+    /// The tree's own mutators call this; it's only needed after changing
+    /// nodes some other way.
+    pub fn rebuild_indexes(&mut self) {
+        self.url_index.clear();
+        let buckets = [
+            (Category::Movie, &self.movies),
+            (Category::Series, &self.series),
+            (Category::LiveStream, &self.live_streams),
+        ];
+        for (category, nodes) in buckets {
+            for (node_index, node) in nodes.iter().enumerate() {
+                for (item_index, item) in node.items.iter().enumerate() {
+                    self.url_index
+                        .entry(item.url.clone())
+                        .or_insert((category.clone(), node_index, item_index));
+                }
+            }
+        }
+    }
+
+    /// Parse M3U content and build the tree in one step
+    pub fn from_m3u(content: &str) -> Result<Self, ParseError> {
+        M3UParser::new(content).parse().map(Self::build)
+    }
+
+    /// Re-categorize all items with a new policy and rebuild the tree
+    ///
+    /// Works from each item's `source_title`, so the raw playlist text
+    /// doesn't need to be kept around.
+    pub fn rebuild_with(&self, policy: &CategorizationPolicy) -> Self {
+        let mut entries: Vec<(M3UItem, usize)> = self
+            .entries()
+            .map(|(item, position)| {
+                let mut item = item.clone();
+                item.recategorize(policy);
+                (item, position)
+            })
+            .collect();
+        entries.sort_by_key(|(_, position)| *position);
+        let items = entries.into_iter().map(|(item, _)| item).collect();
+
+        Self::build_with_options(items, self.options.clone())
+    }
+
+    /// In-place `rebuild_with`: re-categorize all items and regroup them
+    pub fn reclassify(&mut self, policy: &CategorizationPolicy) {
+        *self = self.rebuild_with(policy);
+    }
+
+    pub fn movies(&self) -> &[CategoryNode] {
+        &self.movies
+    }
+
+    pub fn series(&self) -> &[CategoryNode] {
+        &self.series
+    }
+
+    pub fn live_streams(&self) -> &[CategoryNode] {
+        &self.live_streams
+    }
+
+    /// Item with exactly this URL (clone), looked up through the URL index
+    pub fn find_item(&self, url: &str) -> Option<M3UItem> {
+        let (category, node, item) = self.url_index.get(url)?;
+        self.nodes(category).get(*node)?.items.get(*item).cloned()
+    }
+
+    /// Nodes of the given content type
+    pub fn nodes(&self, category: &Category) -> &[CategoryNode] {
+        match category {
+            Category::Movie => &self.movies,
+            Category::Series => &self.series,
+            Category::LiveStream => &self.live_streams,
+        }
+    }
+
+    /// Items of one group, favorites first, then by title and season/episode
+    /// (or playlist order with `ItemSort::InsertionOrder`)
+    ///
+    /// Hidden items are dropped unless `prefs.include_hidden` is set. In
+    /// `prefs.continue_watching` mode in-progress items come before favorites.
+    pub fn get_items(&self, category: &Category, group: &str, prefs: &UserItemPrefs) -> Vec<ItemView> {
+        let Some(node) = self.nodes(category).iter().find(|node| node.name == group) else {
+            return Vec::new();
+        };
+
+        item_views(node.entries(), prefs, self.locale())
+    }
+
+    /// Items of several groups of one content type merged into one list,
+    /// filtered and sorted like `get_items` (unknown names are ignored)
+    pub fn get_items_for_groups(
+        &self,
+        category: &Category,
+        names: &[String],
+        prefs: &UserItemPrefs,
+    ) -> Vec<ItemView> {
+        let items = self
+            .nodes(category)
+            .iter()
+            .filter(|node| names.contains(&node.name))
+            .flat_map(CategoryNode::entries);
+
+        item_views(items, prefs, self.locale())
+    }
+
+    /// Series episodes grouped by cleaned series name instead of group-title
+    ///
+    /// Collects a show split across groups (`Series A`, `Series A 4K`) into one
+    /// node. Names compare case-insensitively; the first spelling seen is kept.
+    pub fn get_series_by_name(&self) -> Vec<CategoryNode> {
+        let mut nodes: Vec<CategoryNode> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+
+        for (item, position) in self.series.iter().flat_map(CategoryNode::entries) {
+            let node = *index.entry(item.title.to_lowercase()).or_insert_with(|| {
+                nodes.push(CategoryNode::new(item.title.clone()));
+                nodes.len() - 1
+            });
+            nodes[node].push(item.clone(), position);
+        }
+
+        nodes
+    }
+
+    /// Number of distinct series, named as in `get_series_by_name`
+    pub fn series_count(&self) -> usize {
+        self.series
+            .iter()
+            .flat_map(|node| node.items.iter())
+            .map(|item| item.title.to_lowercase())
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Every episode of one series (matched case-insensitively on the cleaned
+    /// series name, across all groups), ordered by season then episode
+    pub fn episodes_of(&self, series_name: &str) -> Vec<M3UItem> {
+        let name = series_name.trim().to_lowercase();
+        let mut episodes: Vec<(&M3UItem, usize)> = self
+            .series
+            .iter()
+            .flat_map(CategoryNode::entries)
+            .filter(|(item, _)| item.title.to_lowercase() == name)
+            .collect();
+
+        episodes.sort_by_key(|(item, position)| (item.season, item.episode, *position));
+        episodes.into_iter().map(|(item, _)| item.clone()).collect()
+    }
+
+    /// Case-insensitive search over item titles, and group names if `match_groups` is set
+    pub fn search(&self, query: &str, match_groups: bool) -> SearchResults {
+        let query = query.trim().to_lowercase();
+        let mut results = SearchResults::default();
+        if query.is_empty() {
+            return results;
+        }
+
+        let buckets = [
+            (Category::Movie, &self.movies),
+            (Category::Series, &self.series),
+            (Category::LiveStream, &self.live_streams),
+        ];
+
+        for (category, nodes) in buckets {
+            for node in nodes {
+                if match_groups && node.name.to_lowercase().contains(&query) {
+                    results.groups.push(GroupMatch {
+                        category: category.clone(),
+                        name: node.name.clone(),
+                        count: node.items.len(),
+                    });
+                }
+
+                results.items.extend(
+                    node.items
+                        .iter()
+                        .filter(|item| item.title.to_lowercase().contains(&query))
+                        .cloned(),
+                );
+            }
+        }
+
+        results
+    }
+
+    /// Movie groups with hidden groups removed, sticky groups first
+    pub fn get_movies(&self, sticky: &[String], hidden: &[String]) -> Vec<CategorySummary> {
+        summarize(&self.movies, sticky, hidden, self.locale(), self.options.group_sort)
+    }
+
+    /// Series groups with hidden groups removed, sticky groups first
+    pub fn get_series(&self, sticky: &[String], hidden: &[String]) -> Vec<CategorySummary> {
+        summarize(&self.series, sticky, hidden, self.locale(), self.options.group_sort)
+    }
+
+    /// Live stream groups with hidden groups removed, sticky groups first
+    pub fn get_live_streams(&self, sticky: &[String], hidden: &[String]) -> Vec<CategorySummary> {
+        summarize(&self.live_streams, sticky, hidden, self.locale(), self.options.group_sort)
+    }
+
+    /// All items in tree order (movies, series, live streams)
+    pub fn items(&self) -> impl Iterator<Item = &M3UItem> {
+        self.entries().map(|(item, _)| item)
+    }
+
+    /// `items` with each item's playlist position
+    fn entries(&self) -> impl Iterator<Item = (&M3UItem, usize)> {
+        self.movies
+            .iter()
+            .chain(self.series.iter())
+            .chain(self.live_streams.iter())
+            .flat_map(CategoryNode::entries)
+    }
+
+    /// Items (in tree order) accepted by `predicate`; the first predicate
+    /// error stops the scan and is returned
+    pub fn get_items_filtered<E>(
+        &self,
+        mut predicate: impl FnMut(&M3UItem) -> Result<bool, E>,
+    ) -> Result<Vec<M3UItem>, E> {
+        let mut items = Vec::new();
+        for item in self.items() {
+            if predicate(item)? {
+                items.push(item.clone());
+            }
+        }
+        Ok(items)
+    }
+
+    /// The last `n` items of the playlist across all content types, in
+    /// playlist order (providers usually append new content at the end)
+    pub fn recent_items(&self, n: usize) -> Vec<M3UItem> {
+        let mut entries: Vec<(&M3UItem, usize)> = self.entries().collect();
+        entries.sort_unstable_by_key(|(_, position)| *position);
+        let start = entries.len().saturating_sub(n);
+        entries[start..].iter().map(|(item, _)| (*item).clone()).collect()
+    }
+
+    /// Serialize the whole tree back into an M3U playlist
+    pub fn to_m3u(&self) -> String {
+        to_m3u(self.items())
+    }
+
+    /// Versioned JSON export: `{ schema_version, movies, series, live }`,
+    /// each a list of `{ name, items }` groups in tree order
+    pub fn to_json(&self) -> Result<String, ParseError> {
+        let export = TreeExport {
+            schema_version: TREE_SCHEMA_VERSION,
+            movies: ExportNode::list(&self.movies),
+            series: ExportNode::list(&self.series),
+            live: ExportNode::list(&self.live_streams),
+        };
+        serde_json::to_string(&export).map_err(ParseError::serialization)
+    }
+
+    /// Separate movie, series and live stream M3U playlists
+    pub fn split_by_category(&self) -> SplitPlaylists {
+        let playlist =
+            |nodes: &[CategoryNode]| to_m3u(nodes.iter().flat_map(|node| node.items.iter()));
+        SplitPlaylists {
+            movies: playlist(&self.movies),
+            series: playlist(&self.series),
+            live: playlist(&self.live_streams),
+        }
+    }
+
+    /// M3U playlist of only the items favorited in `prefs`
+    pub fn export_favorites(&self, prefs: &UserItemPrefs) -> String {
+        to_m3u(self.items().filter(|item| prefs.favorites.contains(&item.url)))
+    }
+
+    /// Groups of one content type (filtered and sorted like `get_movies`),
+    /// at most `limit` of them; `None` returns all
+    pub fn get_groups_capped(
+        &self,
+        category: &Category,
+        sticky: &[String],
+        hidden: &[String],
+        limit: Option<usize>,
+    ) -> CappedGroups {
+        let sort = self.options.group_sort;
+        let mut groups = summarize(self.nodes(category), sticky, hidden, self.locale(), sort);
+        let total = groups.len();
+        if let Some(limit) = limit {
+            groups.truncate(limit);
+        }
+
+        CappedGroups {
+            has_more: groups.len() < total,
+            groups,
+            total,
+        }
+    }
+
+    /// Movie, series and live stream groups with the same filtering as the
+    /// individual getters
+    pub fn get_all_categories(&self, sticky: &[String], hidden: &[String]) -> AllCategories {
+        AllCategories {
+            movies: self.get_movies(sticky, hidden),
+            series: self.get_series(sticky, hidden),
+            live_streams: self.get_live_streams(sticky, hidden),
+        }
+    }
+
+    /// Count movies and series per year (items without a year are counted under 0)
+    pub fn year_histogram(&self) -> BTreeMap<u32, usize> {
+        let mut histogram = BTreeMap::new();
+
+        for node in self.movies.iter().chain(self.series.iter()) {
+            for item in &node.items {
+                *histogram.entry(item.year.unwrap_or(0)).or_insert(0) += 1;
+            }
+        }
+
+        histogram
+    }
+
+    /// Movies and series grouped by year, each year sorted by title
+    ///
+    /// Same items as counted by `year_histogram`, for a browse-by-year view.
+    pub fn items_by_year(&self) -> ItemsByYear {
+        let mut grouped = ItemsByYear::default();
+
+        for node in self.movies.iter().chain(self.series.iter()) {
+            for item in &node.items {
+                let bucket = match item.year {
+                    Some(year) => grouped.years.entry(year).or_default(),
+                    None => &mut grouped.unknown,
+                };
+                bucket.push(item.clone());
+            }
+        }
+
+        let locale = self.locale();
+        for bucket in grouped.years.values_mut().chain([&mut grouped.unknown]) {
+            bucket.sort_by(|a, b| compare_names(&a.title, &b.title, locale));
+        }
+
+        grouped
+    }
+
+    /// URLs used by more than one item across the tree, with their counts
+    pub fn duplicate_urls(&self) -> BTreeMap<String, usize> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for item in self.items() {
+            *counts.entry(item.url.as_str()).or_insert(0) += 1;
+        }
+
+        counts
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(url, count)| (url.to_string(), count))
+            .collect()
+    }
+
+    /// Number of items not served over HTTPS
+    pub fn insecure_count(&self) -> usize {
+        self.items().filter(|item| !item.is_secure()).count()
+    }
+}
+
+#[wasm_bindgen]
+impl CategoryTree {
+    /// Build tree from items returned by `parse_m3u`
+    #[wasm_bindgen(js_name = build)]
+    pub fn build_js(items: JsValue) -> Result<CategoryTree, JsValue> {
+        let items: Vec<M3UItem> = serde_wasm_bindgen::from_value(items)
+            .map_err(|e| ParseError::invalid_input("items", e))?;
+        Ok(Self::build(items))
+    }
+
+    /// Build tree with options (see `TreeOptions`)
+    #[wasm_bindgen(js_name = buildWithOptions)]
+    pub fn build_with_options_js(
+        items: JsValue,
+        options: JsValue,
+    ) -> Result<CategoryTree, JsValue> {
+        let items: Vec<M3UItem> = serde_wasm_bindgen::from_value(items)
+            .map_err(|e| ParseError::invalid_input("items", e))?;
+        let options: TreeOptions = serde_wasm_bindgen::from_value(options)
+            .map_err(|e| ParseError::invalid_input("options", e))?;
+        Ok(Self::build_with_options(items, options))
+    }
+
+    /// Re-categorize all items with a new policy (see `CategorizationPolicy`)
+    #[wasm_bindgen(js_name = rebuildWith)]
+    pub fn rebuild_with_js(&self, policy: JsValue) -> Result<CategoryTree, JsValue> {
+        let policy: CategorizationPolicy = serde_wasm_bindgen::from_value(policy)
+            .map_err(|e| ParseError::invalid_input("policy", e))?;
+        Ok(self.rebuild_with(&policy))
+    }
+
+    /// In-place `rebuildWith`
+    #[wasm_bindgen(js_name = reclassify)]
+    pub fn reclassify_js(&mut self, policy: JsValue) -> Result<(), JsValue> {
+        let policy: CategorizationPolicy = serde_wasm_bindgen::from_value(policy)
+            .map_err(|e| ParseError::invalid_input("policy", e))?;
+        self.reclassify(&policy);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = getMovies)]
+    pub fn get_movies_js(&self, sticky: Vec<String>, hidden: Vec<String>) -> Result<JsValue, JsValue> {
+        to_js(&self.get_movies(&sticky, &hidden))
+    }
+
+    #[wasm_bindgen(js_name = getSeries)]
+    pub fn get_series_js(&self, sticky: Vec<String>, hidden: Vec<String>) -> Result<JsValue, JsValue> {
+        to_js(&self.get_series(&sticky, &hidden))
+    }
+
+    #[wasm_bindgen(js_name = getLiveStreams)]
+    pub fn get_live_streams_js(&self, sticky: Vec<String>, hidden: Vec<String>) -> Result<JsValue, JsValue> {
+        to_js(&self.get_live_streams(&sticky, &hidden))
+    }
+
+    /// All three group lists in a single call (one serialization)
+    #[wasm_bindgen(js_name = getAllCategories)]
+    pub fn get_all_categories_js(&self, sticky: Vec<String>, hidden: Vec<String>) -> Result<JsValue, JsValue> {
+        to_js(&self.get_all_categories(&sticky, &hidden))
+    }
+
+    /// At most `limit` groups of one type with a `has_more` flag
+    #[wasm_bindgen(js_name = getGroupsCapped)]
+    pub fn get_groups_capped_js(
+        &self,
+        category: JsValue,
+        sticky: Vec<String>,
+        hidden: Vec<String>,
+        limit: Option<usize>,
+    ) -> Result<JsValue, JsValue> {
+        let category: Category = serde_wasm_bindgen::from_value(category)
+            .map_err(|e| ParseError::invalid_input("category", e))?;
+        to_js(&self.get_groups_capped(&category, &sticky, &hidden, limit))
+    }
+
+    #[wasm_bindgen(js_name = getItems)]
+    pub fn get_items_js(&self, category: JsValue, group: &str, prefs: JsValue) -> Result<JsValue, JsValue> {
+        let category: Category = serde_wasm_bindgen::from_value(category)
+            .map_err(|e| ParseError::invalid_input("category", e))?;
+        let prefs: UserItemPrefs = serde_wasm_bindgen::from_value(prefs)
+            .map_err(|e| ParseError::invalid_input("preferences", e))?;
+        to_js(&self.get_items(&category, group, &prefs))
+    }
+
+    /// Items of several groups merged (e.g. "Action" + "Thriller")
+    #[wasm_bindgen(js_name = getItemsForGroups)]
+    pub fn get_items_for_groups_js(
+        &self,
+        category: JsValue,
+        names: Vec<String>,
+        prefs: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let category: Category = serde_wasm_bindgen::from_value(category)
+            .map_err(|e| ParseError::invalid_input("category", e))?;
+        let prefs: UserItemPrefs = serde_wasm_bindgen::from_value(prefs)
+            .map_err(|e| ParseError::invalid_input("preferences", e))?;
+        to_js(&self.get_items_for_groups(&category, &names, &prefs))
+    }
+
+    #[wasm_bindgen(js_name = search)]
+    pub fn search_js(&self, query: &str, match_groups: bool) -> Result<JsValue, JsValue> {
+        to_js(&self.search(query, match_groups))
+    }
+
+    #[wasm_bindgen(js_name = toM3u)]
+    pub fn to_m3u_js(&self) -> String {
+        self.to_m3u()
+    }
+
+    /// `{ movies, series, live }` M3U playlists
+    #[wasm_bindgen(js_name = splitByCategory)]
+    pub fn split_by_category_js(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.split_by_category())
+    }
+
+    #[wasm_bindgen(js_name = exportFavorites)]
+    pub fn export_favorites_js(&self, prefs: JsValue) -> Result<String, JsValue> {
+        let prefs: UserItemPrefs = serde_wasm_bindgen::from_value(prefs)
+            .map_err(|e| ParseError::invalid_input("preferences", e))?;
+        Ok(self.export_favorites(&prefs))
+    }
+
+    /// Year -> item count map (0 = unknown year)
+    #[wasm_bindgen(js_name = yearHistogram)]
+    pub fn year_histogram_js(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.year_histogram())
+    }
+
+    /// `{ [year]: items, unknown: items }`, titles sorted within each year
+    #[wasm_bindgen(js_name = itemsByYear)]
+    pub fn items_by_year_js(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.items_by_year())
+    }
+
+    /// Series nodes keyed by series name rather than group-title
+    #[wasm_bindgen(js_name = getSeriesByName)]
+    pub fn get_series_by_name_js(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.get_series_by_name())
+    }
+
+    /// Number of distinct series names (not episodes)
+    #[wasm_bindgen(js_name = seriesCount)]
+    pub fn series_count_js(&self) -> usize {
+        self.series_count()
+    }
+
+    /// Episodes of one series across groups, by season and episode
+    #[wasm_bindgen(js_name = episodesOf)]
+    pub fn episodes_of_js(&self, series_name: &str) -> Result<JsValue, JsValue> {
+        to_js(&self.episodes_of(series_name))
+    }
+
+    /// Item with this exact URL, or `undefined`
+    #[wasm_bindgen(js_name = findItem)]
+    pub fn find_item_js(&self, url: &str) -> Result<JsValue, JsValue> {
+        to_js(&self.find_item(url))
+    }
+
+    /// Versioned JSON export for storage (see `TREE_SCHEMA_VERSION`)
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json_js(&self) -> Result<String, JsValue> {
+        Ok(self.to_json()?)
+    }
+
+    /// Items for which the JS `predicate(item)` returns a truthy value
+    ///
+    /// Every item is serialized and passed across the WASM boundary, which
+    /// costs far more than the built-in filters; prefer those (search, groups,
+    /// hidden lists) on large playlists. An exception thrown by the predicate
+    /// aborts the call and is rethrown.
+    #[wasm_bindgen(js_name = getItemsFiltered)]
+    pub fn get_items_filtered_js(&self, predicate: &js_sys::Function) -> Result<JsValue, JsValue> {
+        let items = self.get_items_filtered(|item| {
+            let keep = predicate.call1(&JsValue::NULL, &to_js(item)?)?;
+            Ok::<_, JsValue>(keep.is_truthy())
+        })?;
+        to_js(&items)
+    }
+
+    /// Last `n` playlist items across all groups, for a "recently added" row
+    #[wasm_bindgen(js_name = recentItems)]
+    pub fn recent_items_js(&self, n: usize) -> Result<JsValue, JsValue> {
+        to_js(&self.recent_items(n))
+    }
+
+    /// Merge groups smaller than `minItems` into the uncategorized group
+    #[wasm_bindgen(js_name = collapseSmallGroups)]
+    pub fn collapse_small_groups_js(&mut self, min_items: usize) {
+        self.collapse_small_groups(min_items);
+    }
+
+    /// Switch group order (`"Name"` or `"ByItemCountDesc"`) for later queries
+    #[wasm_bindgen(js_name = setGroupSort)]
+    pub fn set_group_sort_js(&mut self, sort: JsValue) -> Result<(), JsValue> {
+        let sort: GroupSort = serde_wasm_bindgen::from_value(sort)
+            .map_err(|e| ParseError::invalid_input("sort", e))?;
+        self.set_group_sort(sort);
+        Ok(())
+    }
+
+    /// Rebuild the lookup indexes from the current groups
+    #[wasm_bindgen(js_name = rebuildIndexes)]
+    pub fn rebuild_indexes_js(&mut self) {
+        self.rebuild_indexes();
+    }
+
+    /// `{ [url]: count }` for URLs shared by several items
+    #[wasm_bindgen(js_name = duplicateUrls)]
+    pub fn duplicate_urls_js(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.duplicate_urls())
+    }
+
+    /// Number of plain-HTTP (non-HTTPS) streams
+    #[wasm_bindgen(js_name = insecureCount)]
+    pub fn insecure_count_js(&self) -> usize {
+        self.insecure_count()
+    }
+}
+
+/// Per-user views of `items`: hidden ones dropped (or flagged), pinned
+/// (in-progress, favorite) items first, the rest in `prefs.sort` order
+fn item_views<'a>(
+    items: impl Iterator<Item = (&'a M3UItem, usize)>,
+    prefs: &UserItemPrefs,
+    locale: Option<&str>,
+) -> Vec<ItemView> {
+    let mut items: Vec<(ItemView, usize)> = items
+        .filter_map(|(item, position)| {
+            let hidden = prefs.hidden.contains(&item.url);
+            if hidden && !prefs.include_hidden {
+                return None;
+            }
+            let state = prefs.playback.get(&item.url);
+            let view = ItemView {
+                item: item.clone(),
+                hidden,
+                position_secs: state.and_then(|state| state.position_secs),
+                watched: state.and_then(|state| state.watched),
+            };
+            Some((view, position))
+        })
+        .collect();
+
+    let in_progress = |view: &ItemView| {
+        prefs.continue_watching
+            && prefs.playback.get(&view.item.url).is_some_and(PlaybackState::in_progress)
+    };
+    let pinned: HashSet<&str> = {
+        let urls: HashSet<&str> = items.iter().map(|(view, _)| view.item.url.as_str()).collect();
+        prefs
+            .favorites
+            .iter()
+            .map(String::as_str)
+            .filter(|url| urls.contains(url))
+            .take(prefs.favorite_pin_limit.unwrap_or(usize::MAX))
+            .collect()
+    };
+    items.sort_by(|(a, a_position), (b, b_position)| {
+        let a_fav = pinned.contains(a.item.url.as_str());
+        let b_fav = pinned.contains(b.item.url.as_str());
+        in_progress(b)
+            .cmp(&in_progress(a))
+            .then_with(|| b_fav.cmp(&a_fav))
+            .then_with(|| match prefs.sort {
+                ItemSort::Title => compare_names(&a.item.title, &b.item.title, locale)
+                    .then_with(|| a.item.season.cmp(&b.item.season))
+                    .then_with(|| a.item.episode.cmp(&b.item.episode)),
+                ItemSort::InsertionOrder => a_position.cmp(b_position),
+            })
+    });
+
+    items.into_iter().map(|(view, _)| view).collect()
+}
+
+/// Filter hidden groups and order sticky groups (in given order) before the
+/// rest, which follow `sort`
+fn summarize(
+    nodes: &[CategoryNode],
+    sticky: &[String],
+    hidden: &[String],
+    locale: Option<&str>,
+    sort: GroupSort,
+) -> Vec<CategorySummary> {
+    let mut summaries: Vec<CategorySummary> = nodes
+        .iter()
+        .filter(|node| !hidden.contains(&node.name))
+        .map(|node| CategorySummary {
+            name: node.name.clone(),
+            count: node.items.len(),
+            sticky: sticky.contains(&node.name),
+        })
+        .collect();
+
+    summaries.sort_by(|a, b| {
+        let sticky_rank = |s: &CategorySummary| sticky.iter().position(|name| *name == s.name);
+        match (sticky_rank(a), sticky_rank(b)) {
+            (Some(x), Some(y)) => x.cmp(&y),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => match sort {
+                GroupSort::Name => compare_names(&a.name, &b.name, locale),
+                GroupSort::ByItemCountDesc => b
+                    .count
+                    .cmp(&a.count)
+                    .then_with(|| compare_names(&a.name, &b.name, locale)),
+            },
+        }
+    });
+
+    summaries
+}
+
+/// Serialize to plain JS objects (maps become objects, not `Map`)
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| ParseError::serialization(e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_tree(content: &str) -> CategoryTree {
+        CategoryTree::build(M3UParser::new(content).parse().unwrap())
+    }
+
+    #[test]
+    fn test_build_groups_by_type_and_group() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Movie One (2020)
+http://example.com/one.mkv
+#EXTINF:-1 group-title="Drama",Movie Two
+http://example.com/two.mkv
+#EXTINF:-1 group-title="Action",Movie Three
+http://example.com/three.mkv
+#EXTINF:-1,Show S01E01
+http://example.com/show.mkv
+#EXTINF:-1 group-title="News",News Channel
+http://example.com/live/news
+"#);
+
+        assert_eq!(tree.movies().len(), 2);
+        assert_eq!(tree.movies()[0].name, "Action");
+        assert_eq!(tree.movies()[0].items.len(), 2);
+        assert_eq!(tree.series()[0].name, "Uncategorized");
+        assert_eq!(tree.live_streams()[0].name, "News");
+    }
+
+    #[test]
+    fn test_sticky_and_hidden_groups() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Comedy",A
+http://example.com/a.mkv
+#EXTINF:-1 group-title="Action",B
+http://example.com/b.mkv
+#EXTINF:-1 group-title="Drama",C
+http://example.com/c.mkv
+"#);
+
+        let movies = tree.get_movies(&["Drama".to_string()], &["Comedy".to_string()]);
+        let names: Vec<&str> = movies.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["Drama", "Action"]);
+        assert!(movies[0].sticky);
+        assert!(!movies[1].sticky);
+    }
+
+    #[test]
+    fn test_from_m3u_matches_two_step_build() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Movie One (2020)
+http://example.com/one.mkv
+#EXTINF:-1 group-title="Series",Show S01E01
+http://example.com/show.mkv
+#EXTINF:-1 group-title="News",Channel
+http://example.com/live/news
+"#;
+        let tree = CategoryTree::from_m3u(content).unwrap();
+        let two_step = build_tree(content);
+
+        assert_eq!(tree.to_m3u(), two_step.to_m3u());
+        assert_eq!(tree.get_all_categories(&[], &[]), two_step.get_all_categories(&[], &[]));
+        assert!(CategoryTree::from_m3u("not a playlist").is_err());
+    }
+
+    #[test]
+    fn test_year_histogram() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Movies",First (2020)
+http://example.com/first.mkv
+#EXTINF:-1 group-title="Movies",Second (2020)
+http://example.com/second.mkv
+#EXTINF:-1 group-title="Series",Show (2021) S01E01
+http://example.com/show.mkv
+#EXTINF:-1 group-title="Movies",No Year
+http://example.com/noyear.mkv
+#EXTINF:-1 group-title="Live",Channel 2021
+http://example.com/live/channel
+"#);
+
+        let histogram = tree.year_histogram();
+        assert_eq!(histogram.len(), 3);
+        assert_eq!(histogram[&2020], 2);
+        assert_eq!(histogram[&2021], 1);
+        assert_eq!(histogram[&0], 1);
+    }
+
+    #[test]
+    fn test_items_by_year() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Movies",Zebra (2020)
+http://example.com/zebra.mkv
+#EXTINF:-1 group-title="Movies",apple (2020)
+http://example.com/apple.mkv
+#EXTINF:-1 group-title="Series",Show (2021) S01E01
+http://example.com/show.mkv
+#EXTINF:-1 group-title="Movies",No Year
+http://example.com/noyear.mkv
+#EXTINF:-1 group-title="Live",Channel 2021
+http://example.com/live/channel
+"#);
+
+        let grouped = tree.items_by_year();
+        let titles = |items: &[M3UItem]| -> Vec<String> {
+            items.iter().map(|item| item.title.clone()).collect()
+        };
+        assert_eq!(grouped.years.len(), 2);
+        assert_eq!(titles(&grouped.years[&2020]), ["apple", "Zebra"]);
+        assert_eq!(titles(&grouped.years[&2021]), ["Show"]);
+        assert_eq!(titles(&grouped.unknown), ["No Year"]);
+
+        let json = serde_json::to_value(&grouped).unwrap();
+        assert_eq!(json["2020"].as_array().unwrap().len(), 2);
+        assert_eq!(json["unknown"][0]["title"], "No Year");
+    }
+
+    #[test]
+    fn test_series_count() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Series A",Great Show S01E01
+http://example.com/a1.mkv
+#EXTINF:-1 group-title="Series A",Great Show S01E02
+http://example.com/a2.mkv
+#EXTINF:-1 group-title="Series B",Other Show S02E01
+http://example.com/o1.mkv
+#EXTINF:-1 group-title="Series A 4K",great show S01E03
+http://example.com/a3.mkv
+#EXTINF:-1 group-title="Movies",Movie (2020)
+http://example.com/movie.mkv
+"#);
+
+        assert_eq!(tree.series_count(), 2);
+        assert_eq!(tree.series_count(), tree.get_series_by_name().len());
+    }
+
+    #[test]
+    fn test_get_series_by_name_across_groups() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Series A",Great Show S01E01
+http://example.com/a1.mkv
+#EXTINF:-1 group-title="Other",Other Show S01E01
+http://example.com/o1.mkv
+#EXTINF:-1 group-title="Series A 4K",Great Show S01E02
+http://example.com/a2.mkv
+"#);
+
+        assert_eq!(tree.series().len(), 3);
+        let nodes = tree.get_series_by_name();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].name, "Great Show");
+        let urls: Vec<&str> = nodes[0].items.iter().map(|item| item.url.as_str()).collect();
+        assert_eq!(urls, ["http://example.com/a1.mkv", "http://example.com/a2.mkv"]);
+        assert_eq!(nodes[1].name, "Other Show");
+    }
+
+    #[test]
+    fn test_find_item_by_url() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Movie One
+http://example.com/one.mkv
+#EXTINF:-1 group-title="Series",Show S01E02
+http://example.com/show.mkv
+#EXTINF:-1 group-title="News",Channel
+http://example.com/live/news
+"#);
+
+        let item = tree.find_item("http://example.com/show.mkv").unwrap();
+        assert_eq!(item.title, "Show");
+        assert_eq!(item.episode, Some(2));
+        assert_eq!(tree.find_item("http://example.com/live/news").unwrap().title, "Channel");
+        assert!(tree.find_item("http://example.com/missing.mkv").is_none());
+    }
+
+    #[test]
+    fn test_find_item_after_reclassify() {
+        let mut tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Shows",Show S01E02
+http://example.com/movie/show.mkv
+#EXTINF:-1 group-title="Action",Movie One
+http://example.com/movie/one.mkv
+"#);
+        assert_eq!(
+            tree.find_item("http://example.com/movie/show.mkv").unwrap().category,
+            Category::Series
+        );
+
+        tree.reclassify(&CategorizationPolicy {
+            classify_by_url_only: true,
+            ..Default::default()
+        });
+
+        assert!(tree.series().is_empty());
+        let item = tree.find_item("http://example.com/movie/show.mkv").unwrap();
+        assert_eq!(item.category, Category::Movie);
+        assert_eq!(&*item.group, "Shows");
+        let item = tree.find_item("http://example.com/movie/one.mkv").unwrap();
+        assert_eq!(item.title, "Movie One");
+    }
+
+    #[test]
+    fn test_group_case_merges_groups() {
+        let items = M3UParser::new(r#"#EXTM3U
+#EXTINF:-1 group-title="action",Movie One
+http://example.com/one.mkv
+#EXTINF:-1 group-title="ACTION",Movie Two
+http://example.com/two.mkv
+#EXTINF:-1 group-title="sci-fi",Movie Three
+http://example.com/three.mkv
+#EXTINF:-1 group-title="kid's movies",Movie Four
+http://example.com/four.mkv
+"#)
+        .parse()
+        .unwrap();
+        let options = TreeOptions {
+            group_case: GroupCase::Title,
+            ..Default::default()
+        };
+        let tree = CategoryTree::build_with_options(items.clone(), options);
+
+        let names: Vec<&str> = tree.movies().iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(names, ["Action", "Sci-Fi", "Kid's Movies"]);
+        let raw: Vec<&str> = tree.movies()[0].items.iter().map(|item| &*item.group).collect();
+        assert_eq!(raw, ["action", "ACTION"]);
+
+        // Default keeps provider casing
+        assert_eq!(CategoryTree::build(items).movies().len(), 4);
+    }
+
+    #[test]
+    fn test_group_sort_by_item_count() {
+        let mut content = String::from("#EXTM3U\n");
+        for (group, count) in [("Alpha", 2), ("Beta", 10), ("Gamma", 2)] {
+            for i in 0..count {
+                content.push_str(&format!(
+                    "#EXTINF:-1 group-title=\"{}\",{} Movie {}\nhttp://example.com/{}{}.mkv\n",
+                    group, group, i, group, i
+                ));
+            }
+        }
+        let mut tree = build_tree(&content);
+        let names = |groups: Vec<CategorySummary>| -> Vec<String> {
+            groups.into_iter().map(|group| group.name).collect()
+        };
+
+        assert_eq!(names(tree.get_movies(&[], &[])), ["Alpha", "Beta", "Gamma"]);
+
+        tree.set_group_sort(GroupSort::ByItemCountDesc);
+        assert_eq!(names(tree.get_movies(&[], &[])), ["Beta", "Alpha", "Gamma"]);
+        let sticky = ["Gamma".to_string()];
+        assert_eq!(names(tree.get_movies(&sticky, &[])), ["Gamma", "Beta", "Alpha"]);
+    }
+
+    #[test]
+    fn test_duplicate_urls() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Movie One
+http://example.com/shared.mkv
+#EXTINF:-1 group-title="Series",Show S01E01
+http://example.com/shared.mkv
+#EXTINF:-1 group-title="Action",Movie Two
+http://example.com/two.mkv
+"#);
+
+        let duplicates = tree.duplicate_urls();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates["http://example.com/shared.mkv"], 2);
+
+        assert!(build_tree("#EXTM3U\n").duplicate_urls().is_empty());
+    }
+
+    #[test]
+    fn test_collapse_small_groups() {
+        let mut tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Movie One
+http://example.com/one.mkv
+#EXTINF:-1 group-title="Lonely",Movie Two
+http://example.com/two.mkv
+#EXTINF:-1 group-title="Action",Movie Three
+http://example.com/three.mkv
+#EXTINF:-1 group-title="Single",Movie Four
+http://example.com/four.mkv
+"#);
+
+        tree.collapse_small_groups(1);
+        assert_eq!(tree.movies().len(), 3);
+
+        tree.collapse_small_groups(2);
+        let names: Vec<&str> = tree.movies().iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(names, ["Action", UNCATEGORIZED]);
+        assert_eq!(tree.movies()[1].items.len(), 2);
+        assert_eq!(&*tree.movies()[1].items[0].group, "Lonely");
+        assert_eq!(tree.find_item("http://example.com/four.mkv").unwrap().title, "Movie Four");
+
+        // Playlist positions move with the merged items
+        let recent: Vec<String> = tree.recent_items(2).into_iter().map(|item| item.title).collect();
+        assert_eq!(recent, ["Movie Three", "Movie Four"]);
+    }
+
+    #[test]
+    fn test_custom_uncategorized_name() {
+        let items = M3UParser::new(r#"#EXTM3U
+#EXTINF:-1,Movie One
+http://example.com/one.mkv
+#EXTINF:-1 group-title="Single",Movie Two
+http://example.com/two.mkv
+"#).parse().unwrap();
+        let options = TreeOptions {
+            uncategorized_name: "Kategorisiz".to_string(),
+            ..Default::default()
+        };
+
+        let mut tree = CategoryTree::build_with_options(items, options);
+        assert_eq!(tree.movies()[0].name, "Kategorisiz");
+
+        tree.collapse_small_groups(2);
+        assert_eq!(tree.movies().len(), 1);
+        assert_eq!(tree.movies()[0].name, "Kategorisiz");
+        assert_eq!(tree.movies()[0].items.len(), 2);
+
+        let rebuilt = tree.rebuild_with(&CategorizationPolicy::default());
+        assert_eq!(rebuilt.movies()[0].name, "Kategorisiz");
+    }
+
+    #[test]
+    fn test_favorite_pin_limit() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Delta
+http://example.com/delta.mkv
+#EXTINF:-1 group-title="Action",Charlie
+http://example.com/charlie.mkv
+#EXTINF:-1 group-title="Action",Bravo
+http://example.com/bravo.mkv
+#EXTINF:-1 group-title="Action",Alpha
+http://example.com/alpha.mkv
+"#);
+        let mut prefs = UserItemPrefs {
+            favorites: vec![
+                "http://example.com/delta.mkv".to_string(),
+                "http://example.com/charlie.mkv".to_string(),
+                "http://example.com/bravo.mkv".to_string(),
+            ],
+            ..Default::default()
+        };
+        let titles = |prefs: &UserItemPrefs| -> Vec<String> {
+            tree.get_items(&Category::Movie, "Action", prefs)
+                .into_iter()
+                .map(|view| view.item.title)
+                .collect()
+        };
+
+        assert_eq!(titles(&prefs), ["Bravo", "Charlie", "Delta", "Alpha"]);
+
+        prefs.favorite_pin_limit = Some(1);
+        assert_eq!(titles(&prefs), ["Delta", "Alpha", "Bravo", "Charlie"]);
+    }
+
+    #[test]
+    fn test_insertion_order_sort() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Zulu
+http://example.com/zulu.mkv
+#EXTINF:-1 group-title="Drama",Drama One
+http://example.com/drama.mkv
+#EXTINF:-1 group-title="Action",Alpha
+http://example.com/alpha.mkv
+#EXTINF:-1 group-title="Action",Mike
+http://example.com/mike.mkv
+"#);
+        let titles = |prefs: &UserItemPrefs, group: &str| -> Vec<String> {
+            tree.get_items(&Category::Movie, group, prefs)
+                .into_iter()
+                .map(|view| view.item.title)
+                .collect()
+        };
+
+        let prefs = UserItemPrefs::default();
+        assert_eq!(titles(&prefs, "Action"), ["Alpha", "Mike", "Zulu"]);
+
+        let prefs = UserItemPrefs {
+            sort: ItemSort::InsertionOrder,
+            ..Default::default()
+        };
+        assert_eq!(titles(&prefs, "Action"), ["Zulu", "Alpha", "Mike"]);
+    }
+
+    #[test]
+    fn test_get_items_filtered() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Old Movie (2010)
+http://example.com/old.mkv
+#EXTINF:-1 group-title="Action",New Movie (2021)
+http://example.com/new.mkv
+#EXTINF:-1 group-title="News",News Channel
+http://example.com/live/news
+"#);
+
+        let recent = tree
+            .get_items_filtered(|item| Ok::<_, ()>(item.year.is_some_and(|year| year >= 2020)))
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].title, "New Movie");
+
+        let mut calls = 0;
+        let error = tree.get_items_filtered(|_| {
+            calls += 1;
+            Err("stop")
+        });
+        assert_eq!(error.unwrap_err(), "stop");
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_episodes_of() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Series A 4K",Breaking Bad S02E01
+http://example.com/bb201.mkv
+#EXTINF:-1 group-title="Series A",Breaking Bad S01E02
+http://example.com/bb102.mkv
+#EXTINF:-1 group-title="Series A",Other Show S01E01
+http://example.com/other.mkv
+#EXTINF:-1 group-title="Series A",Breaking Bad S01E01
+http://example.com/bb101.mkv
+"#);
+
+        let urls: Vec<String> =
+            tree.episodes_of("breaking bad").into_iter().map(|item| item.url).collect();
+        assert_eq!(urls, [
+            "http://example.com/bb101.mkv",
+            "http://example.com/bb102.mkv",
+            "http://example.com/bb201.mkv",
+        ]);
+        assert!(tree.episodes_of("Missing").is_empty());
+    }
+
+    #[test]
+    fn test_get_items_for_groups() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Zulu
+http://example.com/zulu.mkv
+#EXTINF:-1 group-title="Drama",Drama One
+http://example.com/drama.mkv
+#EXTINF:-1 group-title="Thriller",Alpha
+http://example.com/alpha.mkv
+#EXTINF:-1 group-title="Action",Mike
+http://example.com/mike.mkv
+"#);
+        let prefs = UserItemPrefs {
+            favorites: vec!["http://example.com/mike.mkv".to_string()],
+            hidden: vec!["http://example.com/drama.mkv".to_string()],
+            ..Default::default()
+        };
+
+        let names = ["Action".to_string(), "Thriller".to_string(), "Missing".to_string()];
+        let titles: Vec<String> = tree
+            .get_items_for_groups(&Category::Movie, &names, &prefs)
+            .into_iter()
+            .map(|view| view.item.title)
+            .collect();
+        assert_eq!(titles, ["Mike", "Alpha", "Zulu"]);
+    }
+
+    #[test]
+    fn test_recent_items() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Movie One
+http://example.com/one.mkv
+#EXTINF:-1 group-title="News",News Channel
+http://example.com/live/news
+#EXTINF:-1 group-title="Shows",Show S01E01
+http://example.com/show1.mkv
+#EXTINF:-1 group-title="Action",Movie Two
+http://example.com/two.mkv
+#EXTINF:-1 group-title="Shows",Show S01E02
+http://example.com/show2.mkv
+"#);
+
+        let urls: Vec<String> = tree.recent_items(3).into_iter().map(|item| item.url).collect();
+        assert_eq!(urls, [
+            "http://example.com/show1.mkv",
+            "http://example.com/two.mkv",
+            "http://example.com/show2.mkv",
+        ]);
+        assert_eq!(tree.recent_items(10).len(), 5);
+        assert!(tree.recent_items(0).is_empty());
+    }
+
+    #[test]
+    fn test_turkish_locale_sort() {
+        let mut content = String::from("#EXTM3U\n");
+        for name in ["Şehir", "Zeytin", "Çocuk", "Sahil", "Cadde"] {
+            content.push_str(&format!(
+                "#EXTINF:-1 group-title=\"{}\",{}\nhttp://example.com/{}.mkv\n",
+                name, name, name
+            ));
+        }
+        let items = M3UParser::new(&content).parse().unwrap();
+        let options = TreeOptions {
+            locale: Some("tr".to_string()),
+            ..Default::default()
+        };
+        let tree = CategoryTree::build_with_options(items.clone(), options);
+
+        let names: Vec<String> =
+            tree.get_movies(&[], &[]).into_iter().map(|group| group.name).collect();
+        assert_eq!(names, ["Cadde", "Çocuk", "Sahil", "Şehir", "Zeytin"]);
+
+        // Default order is unchanged: non-ASCII letters after Z
+        let names: Vec<String> = CategoryTree::build(items)
+            .get_movies(&[], &[])
+            .into_iter()
+            .map(|group| group.name)
+            .collect();
+        assert_eq!(names, ["Cadde", "Sahil", "Zeytin", "Çocuk", "Şehir"]);
+    }
+
+    #[test]
+    fn test_get_groups_capped() {
+        let mut content = String::from("#EXTM3U\n");
+        for name in ["E", "D", "C", "B", "A"] {
+            content.push_str(&format!(
+                "#EXTINF:-1 group-title=\"{}\",Movie {}\nhttp://example.com/{}.mkv\n",
+                name, name, name
+            ));
+        }
+        let tree = build_tree(&content);
+
+        let capped = tree.get_groups_capped(&Category::Movie, &[], &[], Some(3));
+        let names: Vec<&str> = capped.groups.iter().map(|group| group.name.as_str()).collect();
+        assert_eq!(names, ["A", "B", "C"]);
+        assert_eq!(capped.total, 5);
+        assert!(capped.has_more);
+
+        let all = tree.get_groups_capped(&Category::Movie, &[], &[], None);
+        assert_eq!(all.groups, tree.get_movies(&[], &[]));
+        assert!(!all.has_more);
+    }
+
+    #[test]
+    fn test_insecure_count() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Live",Secure
+https://example.com/live/secure
+#EXTINF:-1 group-title="Live",Plain
+http://example.com/live/plain
+#EXTINF:-1 group-title="Movies",Movie
+http://example.com/movie.mkv
+"#);
+
+        assert_eq!(tree.insecure_count(), 2);
+    }
+
+    #[test]
+    fn test_get_items_hidden_and_favorites() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Movies",Alpha
+http://example.com/alpha.mkv
+#EXTINF:-1 group-title="Movies",Bravo
+http://example.com/bravo.mkv
+#EXTINF:-1 group-title="Movies",Charlie
+http://example.com/charlie.mkv
+"#);
+
+        let mut prefs = UserItemPrefs {
+            favorites: vec!["http://example.com/charlie.mkv".to_string()],
+            hidden: vec!["http://example.com/alpha.mkv".to_string()],
+            ..Default::default()
+        };
+
+        let items = tree.get_items(&Category::Movie, "Movies", &prefs);
+        let titles: Vec<&str> = items.iter().map(|v| v.item.title.as_str()).collect();
+        assert_eq!(titles, vec!["Charlie", "Bravo"]);
+
+        prefs.include_hidden = true;
+        let items = tree.get_items(&Category::Movie, "Movies", &prefs);
+        let titles: Vec<&str> = items.iter().map(|v| v.item.title.as_str()).collect();
+        assert_eq!(titles, vec!["Charlie", "Alpha", "Bravo"]);
+        assert!(items[1].hidden);
+        assert!(!items[0].hidden && !items[2].hidden);
+    }
+
+    #[test]
+    fn test_get_items_continue_watching() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Movies",Alpha
+http://example.com/alpha.mkv
+#EXTINF:-1 group-title="Movies",Bravo
+http://example.com/bravo.mkv
+#EXTINF:-1 group-title="Movies",Charlie
+http://example.com/charlie.mkv
+"#);
+
+        let mut prefs = UserItemPrefs {
+            favorites: vec!["http://example.com/alpha.mkv".to_string()],
+            ..Default::default()
+        };
+        let state = |position_secs, watched| PlaybackState { position_secs, watched };
+        prefs.playback.insert("http://example.com/charlie.mkv".into(), state(Some(600), None));
+        prefs.playback.insert("http://example.com/bravo.mkv".into(), state(Some(90), Some(true)));
+
+        let items = tree.get_items(&Category::Movie, "Movies", &prefs);
+        let titles: Vec<&str> = items.iter().map(|v| v.item.title.as_str()).collect();
+        assert_eq!(titles, vec!["Alpha", "Bravo", "Charlie"]);
+        assert_eq!(items[2].position_secs, Some(600));
+
+        prefs.continue_watching = true;
+        let items = tree.get_items(&Category::Movie, "Movies", &prefs);
+        let titles: Vec<&str> = items.iter().map(|v| v.item.title.as_str()).collect();
+        assert_eq!(titles, vec!["Charlie", "Alpha", "Bravo"]);
+        assert_eq!(items[2].watched, Some(true));
+    }
+
+    #[test]
+    fn test_search_matches_group_names() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Die Hard
+http://example.com/diehard.mkv
+#EXTINF:-1 group-title="Drama",Action Hero Story
+http://example.com/hero.mkv
+#EXTINF:-1 group-title="Drama",Quiet Film
+http://example.com/quiet.mkv
+"#);
+
+        let titles_only = tree.search("action", false);
+        assert!(titles_only.groups.is_empty());
+        assert_eq!(titles_only.items.len(), 1);
+        assert_eq!(titles_only.items[0].title, "Action Hero Story");
+
+        let with_groups = tree.search("ACTION", true);
+        assert_eq!(with_groups.groups.len(), 1);
+        assert_eq!(with_groups.groups[0].name, "Action");
+        assert_eq!(with_groups.groups[0].category, Category::Movie);
+        assert_eq!(with_groups.groups[0].count, 1);
+
+        let group_only = tree.search("dram", true);
+        assert!(group_only.items.is_empty());
+        assert_eq!(group_only.groups[0].name, "Drama");
+        assert_eq!(group_only.groups[0].count, 2);
+    }
+
+    #[test]
+    fn test_rebuild_with_policy() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Shows",Show (2021) S01E02
+http://example.com/series/1234
+#EXTINF:-1 group-title="News",News Channel
+http://example.com/live/news
+#EXTINF:-1 group-title="Movies",Film
+http://example.com/film.mkv
+"#);
+        assert_eq!(tree.live_streams().len(), 2);
+        assert!(tree.series().is_empty());
+
+        let policy = CategorizationPolicy {
+            live_first: false,
+            ..Default::default()
+        };
+        let rebuilt = tree.rebuild_with(&policy);
+        assert_eq!(rebuilt.live_streams().len(), 1);
+        assert_eq!(rebuilt.series().len(), 1);
+
+        let episode = &rebuilt.series()[0].items[0];
+        assert_eq!(episode.title, "Show");
+        assert_eq!(episode.year, Some(2021));
+        assert_eq!(episode.season, Some(1));
+        assert_eq!(episode.episode, Some(2));
+        assert_eq!(rebuilt.movies()[0].items[0].title, "Film");
+    }
+
+    #[test]
+    fn test_export_favorites() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Movies",Alpha (2020)
+http://example.com/alpha.mkv
+#EXTINF:-1 group-title="Movies",Bravo
+http://example.com/bravo.mkv
+#EXTINF:-1 group-title="Series",Show S01E01
+http://example.com/show.mkv
+#EXTINF:-1 group-title="News",News Channel
+http://example.com/live/news
+"#);
+        let prefs = UserItemPrefs {
+            favorites: vec![
+                "http://example.com/alpha.mkv".to_string(),
+                "http://example.com/live/news".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        let playlist = tree.export_favorites(&prefs);
+        assert!(playlist.starts_with("#EXTM3U\n"));
+
+        let items = M3UParser::new(&playlist).parse().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title, "Alpha");
+        assert_eq!(items[0].year, Some(2020));
+        assert_eq!(items[1].title, "News Channel");
+        assert_eq!(items[1].category, Category::LiveStream);
+    }
+
+    #[test]
+    fn test_node_to_m3u() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 tvg-logo="http://img/a.png" group-title="Action",Movie One (2020)
+http://example.com/one.mkv
+#EXTINF:-1 group-title="Drama",Movie Two
+http://example.com/two.mkv
+#EXTINF:-1 group-title="Action",Movie Three
+http://example.com/three.mkv
+"#);
+        let node = &tree.movies()[0];
+
+        let items = M3UParser::new(&node.to_m3u()).parse().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items, node.items);
+    }
+
+    #[test]
+    fn test_split_by_category() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Movie One (2020)
+http://example.com/one.mkv
+#EXTINF:-1 group-title="Shows",Show S01E01
+http://example.com/show1.mkv
+#EXTINF:-1 group-title="News",News Channel
+http://example.com/live/news
+#EXTINF:-1 group-title="Shows",Show S01E02
+http://example.com/show2.mkv
+"#);
+
+        let split = tree.split_by_category();
+        let parsed = |playlist: &str| M3UParser::new(playlist).parse().unwrap();
+
+        let movies = parsed(&split.movies);
+        assert_eq!(movies.len(), 1);
+        assert!(movies.iter().all(|item| item.category == Category::Movie));
+
+        let series = parsed(&split.series);
+        assert_eq!(series.len(), 2);
+        assert!(series.iter().all(|item| item.category == Category::Series));
+
+        let live = parsed(&split.live);
+        assert_eq!(live.len(), 1);
+        assert!(live.iter().all(|item| item.category == Category::LiveStream));
+    }
+
+    #[test]
+    fn test_get_all_categories_matches_individual_getters() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Comedy",A
+http://example.com/a.mkv
+#EXTINF:-1 group-title="Action",B
+http://example.com/b.mkv
+#EXTINF:-1 group-title="Shows",Show S01E01
+http://example.com/show.mkv
+#EXTINF:-1 group-title="News",News Channel
+http://example.com/live/news
+#EXTINF:-1 group-title="Sports",Sports Channel
+http://example.com/live/sports
+"#);
+        let sticky = vec!["Sports".to_string()];
+        let hidden = vec!["Comedy".to_string()];
+
+        let all = tree.get_all_categories(&sticky, &hidden);
+        assert_eq!(all.movies, tree.get_movies(&sticky, &hidden));
+        assert_eq!(all.series, tree.get_series(&sticky, &hidden));
+        assert_eq!(all.live_streams, tree.get_live_streams(&sticky, &hidden));
+        assert_eq!(all.live_streams[0].name, "Sports");
+    }
+
+    #[test]
+    fn test_to_json_envelope() {
+        let tree = build_tree(r#"#EXTM3U
+#EXTINF:-1 group-title="Action",Movie One
+http://example.com/one.mkv
+#EXTINF:-1 group-title="News",News Channel
+http://example.com/live/news
+"#);
+
+        let json: serde_json::Value = serde_json::from_str(&tree.to_json().unwrap()).unwrap();
+        let mut keys: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, ["live", "movies", "schema_version", "series"]);
+        assert_eq!(json["schema_version"], TREE_SCHEMA_VERSION);
+        assert_eq!(json["movies"][0]["name"], "Action");
+        assert_eq!(json["movies"][0]["items"][0]["title"], "Movie One");
+        assert_eq!(json["live"][0]["name"], "News");
+        assert!(json["series"].as_array().unwrap().is_empty());
+
+        // Schema v1 item shape: changing it requires bumping TREE_SCHEMA_VERSION
+        let item = json["movies"][0]["items"][0].as_object().unwrap();
+        let mut keys: Vec<&str> = item.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(TREE_SCHEMA_VERSION, 1);
+        assert_eq!(
+            keys,
+            [
+                "album", "artwork", "attributes", "audio_variant", "category", "episode",
+                "episode_end", "group", "is_finale", "kodi_props", "logo", "original_url",
+                "season", "source_title", "title", "url", "vlc_options", "year",
+            ]
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use std::cmp::Ordering;
+
+/// Turkish alphabet order (with q/w/x slotted in for foreign titles)
+const TURKISH_ALPHABET: &str = "abcçdefgğhıijklmnoöpqrsştuüvwxyz";
+
+/// Compare two names for display sorting
+///
+/// Without a (supported) locale this is the plain case-insensitive comparison
+/// of lowercased strings. `tr`/`tr-TR` uses Turkish letter order and casing
+/// (`I` -> `ı`, `İ` -> `i`), so `Çocuk` sorts between `Cadde` and `Dere`
+/// instead of after `Z`.
+pub(crate) fn compare_names(a: &str, b: &str, locale: Option<&str>) -> Ordering {
+    match locale {
+        Some(locale) if is_turkish(locale) => turkish_key(a).cmp(&turkish_key(b)),
+        _ => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+fn is_turkish(locale: &str) -> bool {
+    let language = locale.split(['-', '_']).next().unwrap_or(locale);
+    language.eq_ignore_ascii_case("tr")
+}
+
+/// Sort weights: ASCII punctuation and digits first, then the alphabet, then
+/// every other character by code point
+fn turkish_key(name: &str) -> Vec<u32> {
+    let mut key = Vec::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            'I' => key.push(turkish_weight('ı')),
+            'İ' => key.push(turkish_weight('i')),
+            _ => key.extend(c.to_lowercase().map(turkish_weight)),
+        }
+    }
+    key
+}
+
+fn turkish_weight(c: char) -> u32 {
+    match TURKISH_ALPHABET.chars().position(|letter| letter == c) {
+        Some(position) => 0x1000 + position as u32,
+        None if c.is_ascii() => c as u32,
+        None => 0x2000 + c as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(names: &[&str], locale: Option<&str>) -> Vec<String> {
+        let mut names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+        names.sort_by(|a, b| compare_names(a, b, locale));
+        names
+    }
+
+    #[test]
+    fn test_turkish_order() {
+        let names = ["Zeytin", "Şehir", "Çocuk", "Sahil", "Cadde", "İnci", "Irmak", "Dere"];
+        assert_eq!(
+            sorted(&names, Some("tr")),
+            ["Cadde", "Çocuk", "Dere", "Irmak", "İnci", "Sahil", "Şehir", "Zeytin"]
+        );
+        assert_eq!(sorted(&names, Some("tr-TR")), sorted(&names, Some("tr")));
+
+        // Default keeps the plain lowercase comparison
+        let default = sorted(&names, None);
+        assert_eq!(default.last().map(String::as_str), Some("Şehir"));
+        assert_eq!(sorted(&names, Some("en")), default);
+    }
+}
@@ -0,0 +1,111 @@
+use regex::Regex;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    /// Regex pattern for country markers
+    /// Matches: 🇹🇷, (TR), [DE], etc.
+    static ref COUNTRY_PATTERN: Regex =
+        Regex::new(r"([\x{1F1E6}-\x{1F1FF}]{2})|[(\[]\s*([A-Za-z]{2})\s*[)\]]").unwrap();
+}
+
+/// ISO 3166-1 alpha-2 codes, so unrelated two-letter tags like `(TV)` stay
+const ISO_CODES: &str = "\
+    AD AE AF AG AI AL AM AO AQ AR AS AT AU AW AX AZ BA BB BD BE BF BG BH BI BJ BL BM BN BO \
+    BQ BR BS BT BV BW BY BZ CA CC CD CF CG CH CI CK CL CM CN CO CR CU CV CW CX CY CZ DE DJ \
+    DK DM DO DZ EC EE EG EH ER ES ET FI FJ FK FM FO FR GA GB GD GE GF GG GH GI GL GM GN GP \
+    GQ GR GS GT GU GW GY HK HM HN HR HT HU ID IE IL IM IN IO IQ IR IS IT JE JM JO JP KE KG \
+    KH KI KM KN KP KR KW KY KZ LA LB LC LI LK LR LS LT LU LV LY MA MC MD ME MF MG MH MK ML \
+    MM MN MO MP MQ MR MS MT MU MV MW MX MY MZ NA NC NE NF NG NI NL NO NP NR NU NZ OM PA PE \
+    PF PG PH PK PL PM PN PR PS PT PW PY QA RE RO RS RU RW SA SB SC SD SE SG SH SI SJ SK SL \
+    SM SN SO SR SS ST SV SX SY SZ TC TD TF TG TH TJ TK TL TM TN TO TR TT TV TW TZ UA UG UM \
+    US UY UZ VA VC VE VG VI VN VU WF WS YE YT ZA ZM ZW";
+
+/// Common non-ISO spellings mapped to their ISO code
+const CODE_ALIASES: &[(&str, &str)] = &[("UK", "GB")];
+
+/// Codes that collide with other bracketed tags and are only accepted as flags
+const AMBIGUOUS_CODES: &[&str] = &["SD", "TV"];
+
+/// Result of country detection
+#[derive(Debug, Clone, PartialEq)]
+pub struct CountryInfo {
+    /// ISO 3166-1 alpha-2 code, uppercase
+    pub country: String,
+    pub cleaned_title: String,
+}
+
+/// Detect and extract a flag emoji or bracketed country code from title
+///
+/// Flags are decoded from their regional indicator pair; codes must be a
+/// known ISO code (or alias like `UK`) so other bracketed tags are kept.
+///
+/// Examples:
+/// - "🇹🇷 TRT 1" -> CountryInfo { country: "TR", cleaned_title: "TRT 1" }
+/// - "Kanal D (TR)" -> CountryInfo { country: "TR", cleaned_title: "Kanal D" }
+pub fn detect_country(title: &str) -> Option<CountryInfo> {
+    let (full_match, country) = COUNTRY_PATTERN.captures_iter(title).find_map(|captures| {
+        let country = match (captures.get(1), captures.get(2)) {
+            (Some(flag), _) => flag_code(flag.as_str()),
+            (None, Some(code)) => iso_code(code.as_str()),
+            (None, None) => None,
+        }?;
+        Some((captures.get(0)?, country))
+    })?;
+
+    let mut cleaned = String::with_capacity(title.len());
+    cleaned.push_str(&title[..full_match.start()]);
+    cleaned.push(' ');
+    cleaned.push_str(&title[full_match.end()..]);
+
+    let cleaned_title = cleaned.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    Some(CountryInfo {
+        country,
+        cleaned_title,
+    })
+}
+
+/// Two regional indicator symbols -> their letters (`🇹🇷` -> "TR")
+fn flag_code(flag: &str) -> Option<String> {
+    let code: String = flag
+        .chars()
+        .map(|c| char::from_u32(c as u32 - 0x1F1E6 + 'A' as u32))
+        .collect::<Option<_>>()?;
+    ISO_CODES.split_whitespace().any(|known| known == code).then_some(code)
+}
+
+fn iso_code(code: &str) -> Option<String> {
+    let code = code.to_uppercase();
+    if let Some((_, iso)) = CODE_ALIASES.iter().find(|(alias, _)| *alias == code) {
+        return Some(iso.to_string());
+    }
+    if AMBIGUOUS_CODES.contains(&code.as_str()) {
+        return None;
+    }
+    ISO_CODES.split_whitespace().any(|known| known == code).then_some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_emoji() {
+        let info = detect_country("🇹🇷 TRT 1").unwrap();
+        assert_eq!(info.country, "TR");
+        assert_eq!(info.cleaned_title, "TRT 1");
+
+        assert_eq!(detect_country("BBC One 🇬🇧").unwrap().country, "GB");
+    }
+
+    #[test]
+    fn test_bracketed_code() {
+        let info = detect_country("Kanal D (TR)").unwrap();
+        assert_eq!(info.country, "TR");
+        assert_eq!(info.cleaned_title, "Kanal D");
+
+        assert_eq!(detect_country("Sky News [UK]").unwrap().country, "GB");
+        assert!(detect_country("Retro (TV) Classics").is_none());
+        assert!(detect_country("Movie (XY)").is_none());
+    }
+}
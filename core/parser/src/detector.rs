@@ -0,0 +1,296 @@
+use serde::Serialize;
+use crate::country_detector::detect_country;
+use crate::episode_detector::{detect_episode, detect_season, strip_complete_marker, Episode};
+use crate::language_detector::detect_audio_variant;
+use crate::normalizer::{normalize_separators, title_from_url, trim_separators};
+use crate::quality_detector::{detect_quality, has_quality_token};
+use crate::year_detector::detect_year;
+
+/// Working state shared by detectors while a title is being categorized
+///
+/// Each detector reads the current `title`, stores what it extracted and
+/// replaces `title` with its cleaned version for the next stage.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TitleContext {
+    pub title: String,
+    pub url: String,
+    /// Group-title of the entry (empty when unknown)
+    pub group: String,
+    pub year: Option<u32>,
+    pub audio_variant: Option<String>,
+    pub episode: Option<Episode>,
+    /// Season of a whole-season entry (`Show - Season 3`), set only without `episode`
+    pub season: Option<u32>,
+    /// Last season of a box-set range (`Show S01-S03`), set together with `season`
+    pub season_end: Option<u32>,
+    /// Whole-series bundle (`Show Complete Series`), set only without `episode`
+    pub is_complete: bool,
+    /// Bracketed quality tag (`HD`, `FHD`, ...) removed from `title`
+    pub quality: Option<String>,
+    /// ISO country code from a flag or `(TR)` marker removed from a live `title`
+    pub country: Option<String>,
+    /// Report the year but leave it in `title` (`CategorizationPolicy::strip_year_from_title`)
+    pub keep_year_in_title: bool,
+    /// The entry was classified as a live channel: only tag cleanup (quality,
+    /// country) applies, the VOD stages (audio variant, year, episode) leave
+    /// the context alone
+    pub live: bool,
+}
+
+impl TitleContext {
+    pub fn new(title: &str, url: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Single metadata extraction stage of the categorization pipeline
+pub trait Detector: Send + Sync {
+    fn process(&self, ctx: &mut TitleContext);
+}
+
+/// Extracts bracketed quality tags (`[HD]`, `(FHD)`, ...), live channels included
+pub struct QualityDetector;
+
+impl Detector for QualityDetector {
+    fn process(&self, ctx: &mut TitleContext) {
+        if let Some(info) = detect_quality(&ctx.title) {
+            ctx.title = info.cleaned_title;
+            ctx.quality = Some(info.quality);
+        }
+    }
+}
+
+/// Extracts a country flag or `(TR)`-style code, for live channels only:
+/// on VOD titles `(US)` tells remakes apart
+pub struct CountryDetector;
+
+impl Detector for CountryDetector {
+    fn process(&self, ctx: &mut TitleContext) {
+        if !ctx.live {
+            return;
+        }
+        if let Some(info) = detect_country(&ctx.title) {
+            ctx.title = info.cleaned_title;
+            ctx.country = Some(info.country);
+        }
+    }
+}
+
+/// Extracts audio variant markers (Latino, Subtitulado, ...)
+pub struct AudioVariantDetector;
+
+impl Detector for AudioVariantDetector {
+    fn process(&self, ctx: &mut TitleContext) {
+        if ctx.live {
+            return;
+        }
+        if let Some(info) = detect_audio_variant(&ctx.title) {
+            ctx.title = trimmed_title(info.cleaned_title);
+            ctx.audio_variant = Some(info.variant);
+        }
+    }
+}
+
+/// Extracts release year (the title keeps it with `keep_year_in_title`)
+pub struct YearDetector;
+
+impl Detector for YearDetector {
+    fn process(&self, ctx: &mut TitleContext) {
+        if ctx.live {
+            return;
+        }
+        if let Some(info) = detect_year(&ctx.title) {
+            if !ctx.keep_year_in_title {
+                ctx.title = trimmed_title(info.cleaned_title);
+            }
+            ctx.year = Some(info.year);
+        }
+    }
+}
+
+/// Extracts season/episode numbers (title is left untouched, the series
+/// name is carried in the episode)
+///
+/// When the title has no marker, the URL filename is tried instead
+/// (`.../Show.S03E07.mkv`), with dots/underscores read as spaces. A trailing
+/// "Season N" (or an `S01-S03` range) without an episode sets `season` and
+/// cleans the title, a "Complete Series" marker sets `is_complete`. When
+/// the title only has an episode (`Episode 5`), a "Season N" group-title
+/// supplies the season.
+pub struct EpisodeDetector;
+
+impl Detector for EpisodeDetector {
+    fn process(&self, ctx: &mut TitleContext) {
+        if ctx.live || ctx.episode.is_some() {
+            return;
+        }
+
+        ctx.episode = detect_episode(&ctx.title).or_else(|| {
+            let filename = normalize_separators(title_from_url(&ctx.url));
+            let mut episode = detect_episode(&filename)?;
+            if episode.series_name.is_empty() {
+                episode.series_name = ctx.title.clone();
+            }
+            Some(episode)
+        });
+
+        if let Some(episode) = ctx.episode.as_mut() {
+            // May become empty: `M3UItem::apply_categorized` then falls back to the group
+            episode.series_name = trim_separators(&episode.series_name).to_string();
+        }
+
+        if let Some(episode) = ctx.episode.as_mut().filter(|episode| episode.season_implied) {
+            if let Some(info) = detect_season(&ctx.group) {
+                episode.season = info.season;
+                episode.season_implied = false;
+            }
+        }
+
+        if ctx.episode.is_none() {
+            if let Some(cleaned) = strip_complete_marker(&ctx.title) {
+                ctx.title = trimmed_title(cleaned);
+                ctx.is_complete = true;
+            }
+            if let Some(info) = detect_season(&ctx.title) {
+                ctx.title = trimmed_title(info.series_name);
+                ctx.season = Some(info.season);
+                ctx.season_end = info.season_end;
+            }
+        }
+    }
+}
+
+/// Drop separator punctuation left dangling after a marker was cut out
+/// (`Show Name (2022) -` -> `Show Name`); titles that are nothing but
+/// punctuation are kept as they are
+fn trimmed_title(title: String) -> String {
+    match trim_separators(&title) {
+        trimmed if trimmed.len() == title.len() || trimmed.is_empty() => title,
+        trimmed => trimmed.to_string(),
+    }
+}
+
+/// Ordered list of detectors run by the categorizer
+///
+/// `DetectorPipeline::default()` holds the built-in stages
+/// (quality -> country -> audio variant -> year -> episode); stages can be
+/// added, removed or reordered through `detectors`.
+pub struct DetectorPipeline {
+    pub detectors: Vec<Box<dyn Detector>>,
+}
+
+impl DetectorPipeline {
+    /// Pipeline without any stages
+    pub fn empty() -> Self {
+        Self { detectors: Vec::new() }
+    }
+
+    /// Run all stages in order
+    pub fn run(&self, ctx: &mut TitleContext) {
+        for detector in &self.detectors {
+            detector.process(ctx);
+        }
+    }
+}
+
+impl Default for DetectorPipeline {
+    fn default() -> Self {
+        Self {
+            detectors: vec![
+                Box::new(QualityDetector),
+                Box::new(CountryDetector),
+                Box::new(AudioVariantDetector),
+                Box::new(YearDetector),
+                Box::new(EpisodeDetector),
+            ],
+        }
+    }
+}
+
+/// Which metadata markers a raw title carries, see `title_markers`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TitleMarkers {
+    pub has_year: bool,
+    pub has_episode: bool,
+    pub has_quality: bool,
+}
+
+/// Check a title for year, episode and quality markers without categorizing it
+///
+/// Runs the detectors' recognizers on the raw title only, for cheap UI badges.
+pub fn title_markers(title: &str) -> TitleMarkers {
+    TitleMarkers {
+        has_year: detect_year(title).is_some(),
+        has_episode: detect_episode(title).is_some(),
+        has_quality: has_quality_token(title),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_pipeline() {
+        let mut ctx = TitleContext::new("Show (Latino) (2021) S01E02", "http://example.com/a.mkv");
+        DetectorPipeline::default().run(&mut ctx);
+
+        assert_eq!(ctx.audio_variant, Some("Latino".to_string()));
+        assert_eq!(ctx.year, Some(2021));
+        let episode = ctx.episode.unwrap();
+        assert_eq!(episode.series_name, "Show");
+        assert_eq!(episode.season, 1);
+        assert_eq!(episode.episode, 2);
+    }
+
+    #[test]
+    fn test_season_from_group() {
+        let mut ctx = TitleContext::new("Episode 5", "http://example.com/a.mkv");
+        ctx.group = "Show Name Season 2".to_string();
+        DetectorPipeline::default().run(&mut ctx);
+
+        let episode = ctx.episode.unwrap();
+        assert_eq!(episode.season, 2);
+        assert_eq!(episode.episode, 5);
+
+        // An explicit season in the title wins over the group
+        let mut ctx = TitleContext::new("Show S03E01", "http://example.com/a.mkv");
+        ctx.group = "Season 2".to_string();
+        DetectorPipeline::default().run(&mut ctx);
+        assert_eq!(ctx.episode.unwrap().season, 3);
+    }
+
+    #[test]
+    fn test_separator_trim() {
+        let mut ctx = TitleContext::new("Show Name (2022) -", "http://example.com/a.mkv");
+        DetectorPipeline::default().run(&mut ctx);
+        assert_eq!(ctx.title, "Show Name");
+        assert_eq!(ctx.year, Some(2022));
+
+        let mut ctx = TitleContext::new("| Show Name - S01E02", "http://example.com/a.mkv");
+        DetectorPipeline::default().run(&mut ctx);
+        assert_eq!(ctx.episode.unwrap().series_name, "Show Name");
+    }
+
+    #[test]
+    fn test_title_markers() {
+        let markers = title_markers("Show (2020) S01E01 1080p");
+        assert!(markers.has_year);
+        assert!(markers.has_episode);
+        assert!(markers.has_quality);
+
+        assert_eq!(title_markers("Plain Title"), TitleMarkers::default());
+        assert!(title_markers("Channel [HD]").has_quality);
+    }
+
+    #[test]
+    fn test_empty_pipeline_leaves_context() {
+        let mut ctx = TitleContext::new("Movie (2020)", "http://example.com/a.mkv");
+        DetectorPipeline::empty().run(&mut ctx);
+        assert_eq!(ctx, TitleContext::new("Movie (2020)", "http://example.com/a.mkv"));
+    }
+}
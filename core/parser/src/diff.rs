@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use crate::M3UItem;
+
+/// Item present in both playlists whose metadata changed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ItemChange {
+    pub old: M3UItem,
+    pub new: M3UItem,
+}
+
+/// Differences between two parsed playlists, keyed by item URL
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PlaylistDiff {
+    /// Items only in the new playlist (new order)
+    pub added: Vec<M3UItem>,
+    /// Items only in the old playlist (old order)
+    pub removed: Vec<M3UItem>,
+    /// Same URL but a different title, group or logo (new order)
+    pub changed: Vec<ItemChange>,
+}
+
+impl PlaylistDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare two playlists (e.g. yesterday's and today's subscription)
+///
+/// Items are matched by URL; for duplicate URLs the first occurrence wins.
+pub fn diff(old: &[M3UItem], new: &[M3UItem]) -> PlaylistDiff {
+    let mut old_by_url: HashMap<&str, &M3UItem> = HashMap::with_capacity(old.len());
+    for item in old {
+        old_by_url.entry(item.url.as_str()).or_insert(item);
+    }
+    let mut new_by_url: HashMap<&str, &M3UItem> = HashMap::with_capacity(new.len());
+    for item in new {
+        new_by_url.entry(item.url.as_str()).or_insert(item);
+    }
+
+    let mut result = PlaylistDiff::default();
+    for item in new {
+        if !std::ptr::eq(new_by_url[item.url.as_str()], item) {
+            continue;
+        }
+        match old_by_url.get(item.url.as_str()) {
+            None => result.added.push(item.clone()),
+            Some(previous) if is_changed(previous, item) => result.changed.push(ItemChange {
+                old: (*previous).clone(),
+                new: item.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for item in old {
+        let first = std::ptr::eq(old_by_url[item.url.as_str()], item);
+        if first && !new_by_url.contains_key(item.url.as_str()) {
+            result.removed.push(item.clone());
+        }
+    }
+
+    result
+}
+
+fn is_changed(old: &M3UItem, new: &M3UItem) -> bool {
+    old.title != new.title || old.group != new.group || old.logo != new.logo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::M3UParser;
+
+    #[test]
+    fn test_diff_added_removed_changed() {
+        let old = M3UParser::new(
+            "#EXTM3U\n\
+             #EXTINF:-1 group-title=\"Movies\",Kept Movie\nhttp://x/kept.mkv\n\
+             #EXTINF:-1 group-title=\"Movies\",Old Movie\nhttp://x/old.mkv\n\
+             #EXTINF:-1 group-title=\"Movies\",Renamed\nhttp://x/renamed.mkv\n",
+        )
+        .parse()
+        .unwrap();
+        let new = M3UParser::new(
+            "#EXTM3U\n\
+             #EXTINF:-1 group-title=\"Movies\",Kept Movie\nhttp://x/kept.mkv\n\
+             #EXTINF:-1 group-title=\"Movies\",Renamed Again\nhttp://x/renamed.mkv\n\
+             #EXTINF:-1 group-title=\"Movies\",New Movie\nhttp://x/new.mkv\n",
+        )
+        .parse()
+        .unwrap();
+
+        let result = diff(&old, &new);
+        assert_eq!(result.added.len(), 1);
+        assert_eq!(result.added[0].url, "http://x/new.mkv");
+        assert_eq!(result.removed.len(), 1);
+        assert_eq!(result.removed[0].url, "http://x/old.mkv");
+        assert_eq!(result.changed.len(), 1);
+        assert_eq!(result.changed[0].old.title, "Renamed");
+        assert_eq!(result.changed[0].new.title, "Renamed Again");
+
+        assert!(diff(&new, &new).is_empty());
+    }
+}
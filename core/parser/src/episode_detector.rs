@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use regex::Regex;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
@@ -8,21 +9,64 @@ pub struct Episode {
     pub series_name: String,
     pub season: u32,
     pub episode: u32,
+    /// Last episode of a multi-episode entry (S01E01E02, S01E01-E02)
+    pub episode_end: Option<u32>,
+    /// Title carried a season/series finale marker ("(Final)", "Finale", "(Son Bölüm)")
+    pub is_finale: bool,
+    /// Title had no season number (`Episode 5`), `season` defaulted to 1
+    #[serde(default)]
+    pub season_implied: bool,
 }
 
 lazy_static! {
-    /// Regex patterns for episode detection
-    /// Matches: S01E01, S1E1, 1x01, 1x1, Season 1 Episode 1, etc.
-    static ref PATTERNS: Vec<Regex> = vec![
-        // S01E01, S1E1 (with optional spaces)
-        Regex::new(r"(?i)s\s*(\d{1,2})\s*e\s*(\d{1,2})").unwrap(),
-        // 1x01, 1x1
-        Regex::new(r"(?i)(\d{1,2})x(\d{1,2})").unwrap(),
-        // Season 1 Episode 1
-        Regex::new(r"(?i)season\s*(\d{1,2})\s*episode\s*(\d{1,2})").unwrap(),
-        // Episode 1, Ep 1, Ep. 1 (standalone, assumes season 1)
-        Regex::new(r"(?i)ep(?:isode)?\.?\s*(\d{1,2})").unwrap(),
-    ];
+    /// All episode patterns combined into one alternation, searched in a single pass
+    ///
+    /// Alternatives in priority order (lower wins when several match):
+    /// - S01E01, S1E1, S001E005 (with optional spaces; overflowing runs are skipped)
+    ///   and ranges S01 E01-E02, S01E01-02
+    /// - 1x01, 1x1, 1x01-02 (standalone: not glued into words or longer numbers)
+    /// - Season 1 Episode 1
+    /// - Sezon 1 - 05, Season 2 - 10 (season word, episode after a later dash)
+    /// - Episode 1, Ep 1, Ep. 1 (standalone, assumes season 1)
+    /// - Bölüm 12, Bolum 120 (Turkish, standalone, assumes season 1)
+    /// - E15 (standalone, e.g. weekly "Show 2024 E15" after year removal)
+    static ref EPISODE_PATTERN: Regex = Regex::new(concat!(
+        r"(?i)(?P<se>s\s*(?P<se_season>\d+)\s*e\s*(?P<se_episode>\d+)",
+        r"(?:(?:\s*-\s*e\s*|-|e)(?P<se_end>\d{1,3})\b)?)",
+        r"|(?P<x>\b(?P<x_season>\d{1,2})x(?P<x_episode>\d{1,2})",
+        r"(?:-(?:\d{1,2}x)?(?P<x_end>\d{1,2}))?\b)",
+        r"|(?P<long>season\s*(?P<long_season>\d{1,2})\s*episode\s*(?P<long_episode>\d{1,2}))",
+        r"|(?P<dash>(?:sezon|season|temporada)\s*(?P<dash_season>\d{1,2})\b[^\d-]*-\s*",
+        r"(?P<dash_episode>\d{1,3})\b)",
+        r"|(?P<ep>ep(?:isode)?\.?\s*(?P<ep_episode>\d{1,2}))",
+        r"|(?P<bolum>\bb[öo]l[üu]m\s*(?P<bolum_episode>\d{1,3})\b)",
+        r"|(?P<e>\be(?P<e_episode>\d{1,2})\b)",
+    )).unwrap();
+
+    /// Season-only marker at the end of the title (whole-season entries)
+    /// Matches: Season 3, Sezon 2, Temporada 1, (Season 4), 2. Sezon
+    static ref SEASON_ONLY_PATTERN: Regex = Regex::new(concat!(
+        r"(?i)[(\[]?\s*(?:\b(?:season|sezon|temporada|saison|staffel)\s*(\d{1,2})",
+        r"|\b(\d{1,2})\.\s*sezon)\s*[)\]]?\s*$",
+    )).unwrap();
+
+    /// Season range of a box set (`Show S01-S03 Complete`)
+    /// Matches: S01-S03, s1 - s4 (both sides need the S, so `S01-E03` stays an episode)
+    static ref SEASON_RANGE_PATTERN: Regex =
+        Regex::new(r"(?i)\bs(\d{1,2})\s*-\s*s(\d{1,2})\b").unwrap();
+
+    /// Whole-series bundle markers at the end of the title
+    /// Matches: Complete, Complete Series, Full Series, (Full), [Complete]
+    static ref COMPLETE_PATTERN: Regex = Regex::new(concat!(
+        r"(?i)\s*(?:[(\[]\s*(?:complete|full)(?:\s+series)?\s*[)\]]",
+        r"|\b(?:complete(?:\s+series)?|full\s+series))\s*$",
+    )).unwrap();
+
+    /// Finale markers: bracketed anywhere, or bare at the end of the title
+    /// Matches: (Final), [Finale], (Son Bölüm), "... Finale", "... Son Bölüm"
+    static ref FINALE_PATTERN: Regex = Regex::new(
+        r"(?i)\s*(?:[(\[]\s*(?:final|finale|son bölüm)\s*[)\]]|\b(?:finale|son bölüm)\s*$)"
+    ).unwrap();
 }
 
 /// Detect episode information from title using pattern matching
@@ -31,16 +75,109 @@ lazy_static! {
 /// 1. Scans for S/s followed by digits (season)
 /// 2. Then scans for E/e followed by digits (episode)
 /// 3. Extracts series name by finding last non-whitespace before 'S'
+///    (empty when nothing precedes the marker)
 ///
 /// Falls back to regex patterns if manual scan fails.
 pub fn detect_episode(title: &str) -> Option<Episode> {
-    // First try manual character-by-character scan (like TypeScript version)
-    if let Some(ep) = detect_episode_manual(title) {
-        return Some(ep);
+    // Fast path: every episode pattern needs a digit, skip the scans otherwise
+    if !has_digit(title) {
+        return None;
     }
 
-    // Fallback to regex patterns
-    detect_episode_regex(title)
+    // Finale markers must not leak into the series name
+    let (title, is_finale) = strip_finale_marker(title);
+
+    // First try manual character-by-character scan (like TypeScript version),
+    // then fall back to regex patterns
+    let mut episode = detect_episode_manual(&title).or_else(|| detect_episode_regex(&title))?;
+    episode.is_finale = is_finale;
+    Some(episode)
+}
+
+/// Whole-season entry without an episode number (`Show - Season 3`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeasonInfo {
+    pub series_name: String,
+    pub season: u32,
+    /// Last season of a box-set range (`S01-S03`), `None` for a single season
+    pub season_end: Option<u32>,
+}
+
+/// Detect a trailing "Season N" phrase (English or localized) or a season
+/// range with no episode
+///
+/// Only used when `detect_episode` found nothing.
+///
+/// Examples:
+/// - "Show - Season 3" -> SeasonInfo { series_name: "Show", season: 3, .. }
+/// - "Dizi 2. Sezon" -> SeasonInfo { series_name: "Dizi", season: 2, .. }
+/// - "Show S01-S03 Complete" -> SeasonInfo { season: 1, season_end: Some(3), .. }
+pub fn detect_season(title: &str) -> Option<SeasonInfo> {
+    if !has_digit(title) {
+        return None;
+    }
+
+    if let Some(captures) = SEASON_RANGE_PATTERN.captures(title) {
+        let season: u32 = captures[1].parse().ok()?;
+        let season_end: u32 = captures[2].parse().ok()?;
+        if season_end > season {
+            // The bundle marker may also come first (`Show Complete S01-S05`)
+            let series_name = series_prefix(title, captures.get(0)?.start());
+            return Some(SeasonInfo {
+                series_name: strip_complete_marker(&series_name).unwrap_or(series_name),
+                season,
+                season_end: Some(season_end),
+            });
+        }
+    }
+
+    let captures = SEASON_ONLY_PATTERN.captures(title)?;
+    let season = captures.get(1).or_else(|| captures.get(2))?.as_str().parse().ok()?;
+
+    Some(SeasonInfo {
+        series_name: series_prefix(title, captures.get(0)?.start()),
+        season,
+        season_end: None,
+    })
+}
+
+/// Series name in front of a season marker starting at `end`
+fn series_prefix(title: &str, end: usize) -> String {
+    title[..end]
+        .trim_end_matches(|c: char| c.is_whitespace() || "-:|([".contains(c))
+        .trim()
+        .to_string()
+}
+
+/// Remove a trailing "Complete"/"Full Series" bundle marker, returning the
+/// cleaned title if one was found
+///
+/// Examples:
+/// - "Show Name Complete Series" -> Some("Show Name")
+/// - "Show (Full)" -> Some("Show")
+pub fn strip_complete_marker(title: &str) -> Option<String> {
+    let marker = COMPLETE_PATTERN.find(title)?;
+    let cleaned = title[..marker.start()].trim_end_matches(|c: char| c.is_whitespace() || c == '-');
+    (!cleaned.is_empty()).then(|| cleaned.to_string())
+}
+
+/// Cheap byte scan for an ASCII digit
+pub(crate) fn has_digit(title: &str) -> bool {
+    title.bytes().any(|b| b.is_ascii_digit())
+}
+
+/// Remove a finale marker from the title, reporting whether one was found
+fn strip_finale_marker(title: &str) -> (Cow<'_, str>, bool) {
+    match FINALE_PATTERN.find(title) {
+        Some(marker) => {
+            let mut cleaned = String::with_capacity(title.len());
+            cleaned.push_str(&title[..marker.start()]);
+            cleaned.push(' ');
+            cleaned.push_str(&title[marker.end()..]);
+            (Cow::Owned(cleaned.trim().to_string()), true)
+        }
+        None => (Cow::Borrowed(title), false),
+    }
 }
 
 /// Manual character-by-character episode detection (TypeScript algorithm port)
@@ -49,9 +186,9 @@ fn detect_episode_manual(title: &str) -> Option<Episode> {
     let len = chars.len();
     let mut season: Option<u32> = None;
     let mut episode: Option<u32> = None;
+    let mut episode_end: Option<u32> = None;
     let mut series_name_end: usize = 0;
 
-    let is_digit = |ch: char| ch.is_ascii_digit();
     let is_whitespace = |ch: char| ch.is_whitespace();
 
     let mut i = 0;
@@ -61,28 +198,13 @@ fn detect_episode_manual(title: &str) -> Option<Episode> {
         // Look for 'S' or 's' (season marker)
         if season.is_none() && (ch == 'S' || ch == 's') {
             // Try to parse following digits
-            if i + 1 < len {
-                let s0 = chars[i + 1];
-                let s1 = if i + 2 < len { chars[i + 2] } else { '\0' };
-
-                let parsed_season = if is_digit(s0) && is_digit(s1) {
-                    // Two digits
-                    Some(s0.to_digit(10)? * 10 + s1.to_digit(10)?)
-                } else if is_digit(s0) {
-                    // One digit
-                    Some(s0.to_digit(10)?)
-                } else {
-                    None
-                };
+            if let Some((s, _)) = read_number(&chars, i + 1) {
+                season = Some(s);
 
-                if let Some(s) = parsed_season {
-                    season = Some(s);
-
-                    // Find series name end (last non-whitespace before 'S')
-                    series_name_end = i;
-                    while series_name_end > 0 && is_whitespace(chars[series_name_end - 1]) {
-                        series_name_end -= 1;
-                    }
+                // Find series name end (last non-whitespace before 'S')
+                series_name_end = i;
+                while series_name_end > 0 && is_whitespace(chars[series_name_end - 1]) {
+                    series_name_end -= 1;
                 }
             }
         }
@@ -90,24 +212,10 @@ fn detect_episode_manual(title: &str) -> Option<Episode> {
         // Look for 'E' or 'e' (episode marker) - only after season is found
         if season.is_some() && episode.is_none() && (ch == 'E' || ch == 'e') {
             // Try to parse following digits
-            if i + 1 < len {
-                let e0 = chars[i + 1];
-                let e1 = if i + 2 < len { chars[i + 2] } else { '\0' };
-
-                let parsed_episode = if is_digit(e0) && is_digit(e1) {
-                    // Two digits
-                    Some(e0.to_digit(10)? * 10 + e1.to_digit(10)?)
-                } else if is_digit(e0) {
-                    // One digit
-                    Some(e0.to_digit(10)?)
-                } else {
-                    None
-                };
-
-                if let Some(e) = parsed_episode {
-                    episode = Some(e);
-                    break; // Found both season and episode
-                }
+            if let Some((e, next)) = read_number(&chars, i + 1) {
+                episode = Some(e);
+                episode_end = read_episode_end(&chars, next);
+                break; // Found both season and episode
             }
         }
 
@@ -116,58 +224,119 @@ fn detect_episode_manual(title: &str) -> Option<Episode> {
 
     // Both season and episode must be found
     if let (Some(s), Some(e)) = (season, episode) {
-        let series_name = if series_name_end > 0 {
-            chars[..series_name_end].iter().collect::<String>().trim().to_string()
-        } else {
-            title.to_string()
-        };
+        // Empty when the title is just the marker (e.g. "S01E05")
+        let series_name = clean_series_name(&chars[..series_name_end].iter().collect::<String>());
 
         Some(Episode {
             series_name,
             season: s,
             episode: e,
+            episode_end,
+            is_finale: false,
+            season_implied: false,
         })
     } else {
         None
     }
 }
 
-/// Regex-based episode detection (fallback)
-fn detect_episode_regex(title: &str) -> Option<Episode> {
-    for (idx, pattern) in PATTERNS.iter().enumerate() {
-        if let Some(captures) = pattern.captures(title) {
-            let season: u32;
-            let episode: u32;
-
-            // Pattern 3 (Episode only) - assume season 1
-            if idx == 3 {
-                season = 1;
-                episode = captures.get(1)?.as_str().parse().ok()?;
-            } else {
-                season = captures.get(1)?.as_str().parse().ok()?;
-                episode = captures.get(2)?.as_str().parse().ok()?;
-            }
+/// Read the whole digit run at `start` (zero padding included, `001` -> 1),
+/// returning the value and the index after it; `None` if it overflows `u32`
+fn read_number(chars: &[char], start: usize) -> Option<(u32, usize)> {
+    let mut value = chars.get(start)?.to_digit(10)?;
+    let mut end = start + 1;
+    while let Some(digit) = chars.get(end).and_then(|ch| ch.to_digit(10)) {
+        value = value.checked_mul(10)?.checked_add(digit)?;
+        end += 1;
+    }
+    Some((value, end))
+}
 
-            // Extract series name (everything before the match)
-            let match_start = captures.get(0)?.start();
-            let series_name = title[..match_start].trim().to_string();
+/// Read a glued second episode (`E02` or `-E02`) right after the first one
+fn read_episode_end(chars: &[char], start: usize) -> Option<u32> {
+    let mut i = start;
+    if chars.get(i) == Some(&'-') {
+        i += 1;
+    }
+    match chars.get(i) {
+        Some('E') | Some('e') => read_number(chars, i + 1).map(|(value, _)| value),
+        _ => None,
+    }
+}
 
-            // If series name is empty, use full title
-            let series_name = if series_name.is_empty() {
-                title.to_string()
-            } else {
-                series_name
-            };
+/// Named alternatives of `EPISODE_PATTERN` in priority order, with whether
+/// they carry a season (episode-only alternatives assume season 1)
+const EPISODE_ALTERNATIVES: [(&str, bool); 7] = [
+    ("se", true),
+    ("x", true),
+    ("long", true),
+    ("dash", true),
+    ("ep", false),
+    ("bolum", false),
+    ("e", false),
+];
 
-            return Some(Episode {
-                series_name,
-                season,
-                episode,
-            });
+/// Regex-based episode detection (fallback)
+///
+/// Walks the matches of the combined pattern once and keeps the one from the
+/// highest-priority alternative (leftmost among equals). A match whose numbers
+/// overflow `u32` is skipped, so a lower alternative can still win.
+fn detect_episode_regex(title: &str) -> Option<Episode> {
+    let mut best: Option<(usize, Episode)> = None;
+    for captures in EPISODE_PATTERN.captures_iter(title) {
+        let Some(rank) = EPISODE_ALTERNATIVES
+            .iter()
+            .position(|(name, _)| captures.name(name).is_some())
+        else {
+            continue;
+        };
+        if best.as_ref().is_some_and(|(best_rank, _)| rank >= *best_rank) {
+            continue;
+        }
+        let Some(episode) = episode_from_captures(title, &captures, rank) else {
+            continue;
+        };
+        best = Some((rank, episode));
+        if rank == 0 {
+            break;
         }
     }
 
-    None
+    best.map(|(_, episode)| episode)
+}
+
+/// Build an `Episode` from a match of the `rank`-th alternative
+fn episode_from_captures(title: &str, captures: &regex::Captures, rank: usize) -> Option<Episode> {
+    let (name, has_season) = EPISODE_ALTERNATIVES[rank];
+    let number = |suffix: &str| -> Option<u32> {
+        captures.name(&format!("{}_{}", name, suffix))?.as_str().parse().ok()
+    };
+    let season = if has_season { number("season")? } else { 1 };
+    let episode = number("episode")?;
+    // Alternatives without an `_end` group never report a range
+    let episode_end = number("end").filter(|end| *end > episode);
+
+    // Extract series name (everything before the match, may be empty)
+    let match_start = captures.get(0)?.start();
+    let series_name = clean_series_name(&title[..match_start]);
+
+    Some(Episode {
+        series_name,
+        season,
+        episode,
+        episode_end,
+        is_finale: false,
+        season_implied: !has_season,
+    })
+}
+
+/// Trim whitespace and a dangling opening bracket left by `(S1E3)` / `[1x03]` markers
+fn clean_series_name(prefix: &str) -> String {
+    prefix
+        .trim_end()
+        .trim_end_matches(['(', '['])
+        .trim()
+        .to_string()
 }
 
 #[cfg(test)]
@@ -227,6 +396,21 @@ mod tests {
         assert_eq!(ep.episode, 34);
     }
 
+    #[test]
+    fn test_spaced_season_episode() {
+        for ep in [detect_episode("Show S01 E01"), detect_episode_regex("Show S01 E01")] {
+            let ep = ep.unwrap();
+            assert_eq!(ep.series_name, "Show");
+            assert_eq!(ep.season, 1);
+            assert_eq!(ep.episode, 1);
+        }
+
+        let ep = detect_episode("Show S02  E10 Title").unwrap();
+        assert_eq!(ep.series_name, "Show");
+        assert_eq!(ep.season, 2);
+        assert_eq!(ep.episode, 10);
+    }
+
     #[test]
     fn test_episode_only_pattern() {
         let ep = detect_episode("Show Episode 5").unwrap();
@@ -240,4 +424,275 @@ mod tests {
         assert_eq!(ep.season, 1);
         assert_eq!(ep.episode, 3);
     }
+
+    #[test]
+    fn test_bracketed_marker() {
+        let ep = detect_episode("My Show (S1E3)").unwrap();
+        assert_eq!(ep.series_name, "My Show");
+        assert_eq!(ep.season, 1);
+        assert_eq!(ep.episode, 3);
+
+        let ep = detect_episode("My Show [1x03]").unwrap();
+        assert_eq!(ep.series_name, "My Show");
+        assert_eq!(ep.season, 1);
+        assert_eq!(ep.episode, 3);
+    }
+
+    #[test]
+    fn test_standalone_e_marker() {
+        let ep = detect_episode("Show E15").unwrap();
+        assert_eq!(ep.series_name, "Show");
+        assert_eq!(ep.season, 1);
+        assert_eq!(ep.episode, 15);
+
+        assert!(detect_episode("Formula Evolution").is_none());
+    }
+
+    #[test]
+    fn test_multi_episode() {
+        let ep = detect_episode("Show S01E01E02").unwrap();
+        assert_eq!(ep.series_name, "Show");
+        assert_eq!(ep.season, 1);
+        assert_eq!(ep.episode, 1);
+        assert_eq!(ep.episode_end, Some(2));
+
+        let ep = detect_episode("Show S02E05-E06").unwrap();
+        assert_eq!(ep.episode, 5);
+        assert_eq!(ep.episode_end, Some(6));
+
+        let ep = detect_episode("Show S01E01 Extended").unwrap();
+        assert_eq!(ep.episode_end, None);
+    }
+
+    #[test]
+    fn test_regex_multi_episode() {
+        let ep = detect_episode_regex("Show S01 E01-E02").unwrap();
+        assert_eq!((ep.season, ep.episode, ep.episode_end), (1, 1, Some(2)));
+
+        let ep = detect_episode_regex("Show S 02 E 05-06").unwrap();
+        assert_eq!((ep.season, ep.episode, ep.episode_end), (2, 5, Some(6)));
+
+        let ep = detect_episode_regex("Show 1x01-02").unwrap();
+        assert_eq!(ep.series_name, "Show");
+        assert_eq!((ep.season, ep.episode, ep.episode_end), (1, 1, Some(2)));
+        assert_eq!(detect_episode_regex("Show 3x07-3x08").unwrap().episode_end, Some(8));
+
+        // A dash before an unrelated number or a resolution is not a range
+        assert_eq!(detect_episode_regex("Show S01 E05 - 10 Things").unwrap().episode_end, None);
+        assert_eq!(detect_episode_regex("Show 1x05-1080p").unwrap().episode_end, None);
+        assert_eq!(detect_episode_regex("Show 1x05-03").unwrap().episode_end, None);
+    }
+
+    #[test]
+    fn test_bare_marker_has_empty_series_name() {
+        let ep = detect_episode("S01E05").unwrap();
+        assert_eq!(ep.series_name, "");
+        assert_eq!(ep.season, 1);
+        assert_eq!(ep.episode, 5);
+
+        let ep = detect_episode("Episode 7").unwrap();
+        assert_eq!(ep.series_name, "");
+        assert_eq!(ep.episode, 7);
+    }
+
+    #[test]
+    fn test_finale_markers() {
+        let ep = detect_episode("Show (Final) S02E10").unwrap();
+        assert_eq!(ep.series_name, "Show");
+        assert_eq!(ep.episode, 10);
+        assert!(ep.is_finale);
+
+        let ep = detect_episode("Show S02E10 Finale").unwrap();
+        assert_eq!(ep.series_name, "Show");
+        assert!(ep.is_finale);
+
+        let ep = detect_episode("Dizi (Son Bölüm) Episode 40").unwrap();
+        assert_eq!(ep.series_name, "Dizi");
+        assert_eq!(ep.episode, 40);
+        assert!(ep.is_finale);
+
+        let ep = detect_episode("Final Space S01E01").unwrap();
+        assert_eq!(ep.series_name, "Final Space");
+        assert!(!ep.is_finale);
+    }
+
+    #[test]
+    fn test_fast_path_without_digits() {
+        assert!(!has_digit("Show Episode Finale"));
+        assert!(detect_episode("Show Episode Finale").is_none());
+        assert!(detect_episode("Season Episode").is_none());
+        assert!(has_digit("Show S1E1"));
+    }
+
+    #[test]
+    fn test_season_word_with_dash_episode() {
+        let ep = detect_episode("Dizi Sezon 1 - 05").unwrap();
+        assert_eq!(ep.series_name, "Dizi");
+        assert_eq!(ep.season, 1);
+        assert_eq!(ep.episode, 5);
+
+        let ep = detect_episode("Anime Season 2 (TR Altyazı) - 113").unwrap();
+        assert_eq!(ep.series_name, "Anime");
+        assert_eq!(ep.season, 2);
+        assert_eq!(ep.episode, 113);
+    }
+
+    #[test]
+    fn test_season_only() {
+        assert!(detect_episode("Show - Season 3").is_none());
+
+        let info = detect_season("Show - Season 3").unwrap();
+        assert_eq!(info.series_name, "Show");
+        assert_eq!(info.season, 3);
+
+        let info = detect_season("Dizi 2. Sezon").unwrap();
+        assert_eq!(info.series_name, "Dizi");
+        assert_eq!(info.season, 2);
+
+        assert_eq!(detect_season("Show (Season 4)").unwrap().season, 4);
+        assert!(detect_season("Season of the Witch").is_none());
+        assert!(detect_season("Show Season 2 Extras").is_none());
+    }
+
+    #[test]
+    fn test_season_range() {
+        assert!(detect_episode("Show S01-S03").is_none());
+
+        let info = detect_season("Show S01-S03").unwrap();
+        assert_eq!(info.series_name, "Show");
+        assert_eq!(info.season, 1);
+        assert_eq!(info.season_end, Some(3));
+
+        let info = detect_season("Show - S1-S4 Complete").unwrap();
+        assert_eq!(info.series_name, "Show");
+        assert_eq!(info.season_end, Some(4));
+        assert_eq!(detect_season("Show Complete S01-S05").unwrap().series_name, "Show");
+
+        // Not a range
+        assert!(detect_episode("Show S01-E03").is_some());
+        assert_eq!(detect_season("Show - Season 3").unwrap().season_end, None);
+    }
+
+    #[test]
+    fn test_complete_marker() {
+        assert_eq!(strip_complete_marker("Show Name Complete").as_deref(), Some("Show Name"));
+        let cleaned = strip_complete_marker("Show Name Complete Series");
+        assert_eq!(cleaned.as_deref(), Some("Show Name"));
+        assert_eq!(strip_complete_marker("Show - Full Series").as_deref(), Some("Show"));
+        assert_eq!(strip_complete_marker("Show (Full)").as_deref(), Some("Show"));
+        assert!(strip_complete_marker("The Complete Works").is_none());
+        assert!(strip_complete_marker("Full Metal Jacket").is_none());
+        assert!(strip_complete_marker("Complete").is_none());
+    }
+
+    #[test]
+    fn test_x_pattern_word_boundaries() {
+        assert!(detect_episode("Matrix Reloaded").is_none());
+        assert!(detect_episode("Lens 100x Zoom").is_none());
+        assert!(detect_episode("Model 100x200").is_none());
+        assert!(detect_episode("Box 12").is_none());
+        assert!(detect_episode("Res1x05").is_none());
+
+        let result = detect_episode("Show 1x05").unwrap();
+        assert_eq!(result.series_name, "Show");
+        assert_eq!(result.season, 1);
+        assert_eq!(result.episode, 5);
+    }
+
+    #[test]
+    fn test_standalone_bolum() {
+        let ep = detect_episode("Dizi Bölüm 12").unwrap();
+        assert_eq!(ep.series_name, "Dizi");
+        assert_eq!(ep.season, 1);
+        assert_eq!(ep.episode, 12);
+        assert!(ep.season_implied);
+
+        assert_eq!(detect_episode("Uzun Dizi BÖLÜM 154").unwrap().episode, 154);
+        assert_eq!(detect_episode("Dizi Bolum 3").unwrap().episode, 3);
+    }
+
+    #[test]
+    fn test_zero_padded_markers() {
+        let result = detect_episode("Show S001E005").unwrap();
+        assert_eq!(result.series_name, "Show");
+        assert_eq!(result.season, 1);
+        assert_eq!(result.episode, 5);
+
+        let result = detect_episode("Show S010E099").unwrap();
+        assert_eq!(result.season, 10);
+        assert_eq!(result.episode, 99);
+
+        let result = detect_episode("Show S01E105").unwrap();
+        assert_eq!(result.episode, 105);
+    }
+
+    #[test]
+    fn test_overflowing_digit_run() {
+        let digits = "9".repeat(40);
+        assert!(detect_episode(&format!("Show S{}E01", digits)).is_none());
+        assert!(detect_episode(&format!("Show S01E{}", digits)).is_none());
+
+        // u32::MAX still fits, one more does not
+        assert_eq!(detect_episode("Show S01E4294967295").unwrap().episode, u32::MAX);
+        assert!(detect_episode("Show S01E4294967296").is_none());
+    }
+
+    /// Previous one-regex-per-pattern fallback, kept as the parity reference
+    fn detect_episode_multi_regex(title: &str) -> Option<Episode> {
+        let patterns = [
+            r"(?i)s\s*(\d{1,2})\s*e\s*(\d{1,2})",
+            r"(?i)(\d{1,2})x(\d{1,2})",
+            r"(?i)season\s*(\d{1,2})\s*episode\s*(\d{1,2})",
+            r"(?i)ep(?:isode)?\.?\s*(\d{1,2})",
+            r"(?i)\be(\d{1,2})\b",
+        ];
+        for pattern in patterns.iter().map(|p| Regex::new(p).unwrap()) {
+            if let Some(captures) = pattern.captures(title) {
+                let (season, episode) = if pattern.captures_len() == 2 {
+                    (1, captures[1].parse().ok()?)
+                } else {
+                    (captures[1].parse().ok()?, captures[2].parse().ok()?)
+                };
+                return Some(Episode {
+                    series_name: clean_series_name(&title[..captures.get(0)?.start()]),
+                    season,
+                    episode,
+                    episode_end: None,
+                    is_finale: false,
+                    season_implied: pattern.captures_len() == 2,
+                });
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_combined_regex_parity() {
+        let titles = [
+            "Show S01E02",
+            "Show s 1 e 2",
+            "Show 1x03",
+            "Show (2x10)",
+            "Show Season 2 Episode 4",
+            "Show Episode 7",
+            "Show Ep. 3",
+            "Show 2024 E15",
+            "Show Ep 5 2x07",
+            "Episode 3 of Show 1x04",
+            "E5 Show Season 1 Episode 6",
+            "Show Extra 12",
+            "Movie Title",
+            "[3x01] Show",
+            // Overflowing season run: the `se` match is skipped for the `x` one
+            "Show S 99999999999 E 01 2x03",
+        ];
+        for title in titles {
+            assert_eq!(
+                detect_episode_regex(title),
+                detect_episode_multi_regex(title),
+                "parity mismatch for {:?}",
+                title
+            );
+        }
+    }
 }
@@ -0,0 +1,88 @@
+use std::fmt;
+use serde::Serialize;
+use wasm_bindgen::JsValue;
+
+/// Machine-readable error kind, serialized as its name (`"MissingHeader"`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ErrorCode {
+    /// First line is not `#EXTM3U`
+    MissingHeader,
+    /// Input has no lines at all
+    EmptyFile,
+    /// Options/preferences/items passed from JS could not be deserialized
+    InvalidInput,
+    /// Unknown `encoding` label
+    UnsupportedEncoding,
+    /// Reading from the underlying source failed
+    Io,
+    /// Result could not be converted to a JS value
+    Serialization,
+}
+
+/// Error returned by the parser and the wasm API
+///
+/// Crosses the wasm boundary as `{ code, message, line }` so JS can branch
+/// on `code` instead of matching message strings.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParseError {
+    pub code: ErrorCode,
+    pub message: String,
+    /// 1-based line the error refers to, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+impl ParseError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            line: None,
+        }
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub(crate) fn missing_header() -> Self {
+        Self::new(ErrorCode::MissingHeader, "Invalid M3U file: missing #EXTM3U header").at_line(1)
+    }
+
+    pub(crate) fn empty_file() -> Self {
+        Self::new(ErrorCode::EmptyFile, "Empty file")
+    }
+
+    /// `InvalidInput` error for a value that failed to deserialize
+    pub(crate) fn invalid_input(what: &str, error: impl fmt::Display) -> Self {
+        Self::new(ErrorCode::InvalidInput, format!("Invalid {}: {}", what, error))
+    }
+
+    pub(crate) fn serialization(error: impl fmt::Display) -> Self {
+        Self::new(ErrorCode::Serialization, format!("Serialization error: {}", error))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{} (line {})", self.message, line),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for String {
+    fn from(error: ParseError) -> Self {
+        error.to_string()
+    }
+}
+
+impl From<ParseError> for JsValue {
+    fn from(error: ParseError) -> Self {
+        serde_wasm_bindgen::to_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.message))
+    }
+}
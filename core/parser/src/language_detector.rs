@@ -0,0 +1,87 @@
+use regex::Regex;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    /// Regex pattern for audio variant markers
+    /// Matches: (Latino), [Castellano], Subtitulado, Dublado, etc.
+    static ref AUDIO_VARIANT_PATTERN: Regex =
+        Regex::new(r"(?i)[(\[]?\b(latino|castellano|subtitulado|dublado)\b[)\]]?").unwrap();
+}
+
+/// Result of audio variant detection
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioVariantInfo {
+    pub variant: String,
+    pub cleaned_title: String,
+}
+
+/// Detect and extract audio variant (dub/sub language) from title
+///
+/// Removes the marker and surrounding parentheses/brackets from title,
+/// so variants of the same content end up with identical titles.
+///
+/// Examples:
+/// - "Movie (Latino)" -> AudioVariantInfo { variant: "Latino", cleaned_title: "Movie" }
+/// - "Movie [Subtitulado]" -> AudioVariantInfo { variant: "Subtitulado", cleaned_title: "Movie" }
+pub fn detect_audio_variant(title: &str) -> Option<AudioVariantInfo> {
+    let captures = AUDIO_VARIANT_PATTERN.captures(title)?;
+    let full_match = captures.get(0)?;
+    let marker = captures.get(1)?.as_str().to_lowercase();
+
+    // Normalize to capitalized form regardless of source casing
+    let mut chars = marker.chars();
+    let variant = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => return None,
+    };
+
+    let mut cleaned = String::with_capacity(title.len());
+    cleaned.push_str(&title[..full_match.start()]);
+    cleaned.push(' ');
+    cleaned.push_str(&title[full_match.end()..]);
+
+    let cleaned_title = cleaned.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    Some(AudioVariantInfo {
+        variant,
+        cleaned_title,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latino_in_parentheses() {
+        let info = detect_audio_variant("Movie (Latino)").unwrap();
+        assert_eq!(info.variant, "Latino");
+        assert_eq!(info.cleaned_title, "Movie");
+    }
+
+    #[test]
+    fn test_variants_clean_to_same_title() {
+        let latino = detect_audio_variant("Great Movie (Latino)").unwrap();
+        let subbed = detect_audio_variant("Great Movie [SUBTITULADO]").unwrap();
+        assert_eq!(latino.cleaned_title, subbed.cleaned_title);
+        assert_eq!(latino.variant, "Latino");
+        assert_eq!(subbed.variant, "Subtitulado");
+    }
+
+    #[test]
+    fn test_standalone_marker() {
+        let info = detect_audio_variant("Filme Dublado").unwrap();
+        assert_eq!(info.variant, "Dublado");
+        assert_eq!(info.cleaned_title, "Filme");
+
+        let info = detect_audio_variant("Castellano Pelicula").unwrap();
+        assert_eq!(info.variant, "Castellano");
+        assert_eq!(info.cleaned_title, "Pelicula");
+    }
+
+    #[test]
+    fn test_no_variant() {
+        assert!(detect_audio_variant("Just a Movie").is_none());
+        assert!(detect_audio_variant("Latinos Unidos").is_none());
+    }
+}
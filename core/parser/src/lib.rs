@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
@@ -5,22 +7,75 @@ mod parser;
 mod categorizer;
 mod episode_detector;
 mod year_detector;
+mod language_detector;
+mod quality_detector;
+mod country_detector;
+mod category_tree;
+mod collation;
+mod detector;
+mod normalizer;
+mod writer;
+mod diff;
+mod arena;
+mod error;
+mod metrics;
+#[cfg(feature = "reader")]
+mod reader;
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+mod parallel;
 
-pub use parser::M3UParser;
-pub use categorizer::{Category, categorize_item, CategorizedItem};
-pub use episode_detector::{Episode, detect_episode};
+pub use parser::{M3UParser, ParseResult, ParseWarning, ParserOptions};
+#[cfg(feature = "reader")]
+pub use reader::{parse_reader, parse_reader_with_options, ReaderItems};
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+pub use parallel::parse_parallel;
+pub use categorizer::{
+    Category, categorize_item, categorize_item_in_group, categorize_item_with_duration,
+    categorize_item_with_pipeline, categorize_item_with_policy, CategorizationPolicy,
+    CategorizedItem, Confidence,
+};
+pub use detector::{
+    title_markers, AudioVariantDetector, CountryDetector, Detector, DetectorPipeline,
+    EpisodeDetector, QualityDetector, TitleContext, TitleMarkers, YearDetector,
+};
+pub use episode_detector::{
+    Episode, detect_episode, detect_season, strip_complete_marker, SeasonInfo,
+};
 pub use year_detector::{detect_year, YearInfo};
+pub use language_detector::{detect_audio_variant, AudioVariantInfo};
+pub use quality_detector::{detect_quality, QualityInfo};
+pub use country_detector::{detect_country, CountryInfo};
+pub use normalizer::{
+    encode_url_spaces, normalize_separators, normalize_unicode, normalize_url, split_group_list,
+    strip_leading_symbols, trim_separators, url_filename,
+};
+pub use writer::to_m3u;
+pub use diff::{diff, ItemChange, PlaylistDiff};
+pub use arena::ParserArena;
+pub use error::{ErrorCode, ParseError};
+pub use metrics::{estimate_items, parse_with_metrics, MeasuredParse, ParseMetrics};
+pub use category_tree::{
+    AllCategories, CappedGroups, CategoryNode, CategorySummary, CategoryTree, GroupCase, GroupMatch,
+    GroupSort, ItemSort, ItemView, ItemsByYear, PlaybackState, SearchResults, SplitPlaylists,
+    TreeOptions, UserItemPrefs, TREE_SCHEMA_VERSION,
+};
 
 /// Represents a parsed M3U item
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[wasm_bindgen(getter_with_clone)]
 pub struct M3UItem {
     #[wasm_bindgen(skip)]
     pub title: String,
     #[wasm_bindgen(skip)]
     pub url: String,
+    /// URL as written in the playlist, kept when `normalize_url` or
+    /// `encode_url_spaces` changed it
     #[wasm_bindgen(skip)]
-    pub group: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_url: Option<String>,
+    /// Group-title, shared between all items of the same group (see `M3UParser`)
+    #[wasm_bindgen(skip)]
+    pub group: Arc<str>,
     #[wasm_bindgen(skip)]
     pub logo: Option<String>,
     #[wasm_bindgen(skip)]
@@ -29,8 +84,174 @@ pub struct M3UItem {
     pub year: Option<u32>,
     #[wasm_bindgen(skip)]
     pub season: Option<u32>,
+    /// Last season of a box-set range (`Show S01-S03`)
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub season_end: Option<u32>,
     #[wasm_bindgen(skip)]
     pub episode: Option<u32>,
+    #[wasm_bindgen(skip)]
+    pub episode_end: Option<u32>,
+    #[wasm_bindgen(skip)]
+    #[serde(default)]
+    pub is_finale: bool,
+    /// Whole-series bundle (`Show Complete Series`)
+    #[wasm_bindgen(skip)]
+    #[serde(default)]
+    pub is_complete: bool,
+    #[wasm_bindgen(skip)]
+    pub audio_variant: Option<String>,
+    /// Bracketed quality tag (`HD`, `FHD`, `4K`, ...) removed from the title
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    /// ISO country code from a flag emoji or `(TR)` marker removed from a
+    /// live channel's title
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// `#EXTINF` duration in seconds (`-1` for live/unknown)
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f64>,
+    /// Title as written in the playlist, kept when a prefix or leading emoji were
+    /// stripped (`ParserOptions::strip_prefix`, `ParserOptions::strip_leading_emoji`)
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_title: Option<String>,
+    /// Title fed to the categorizer (before year/episode/variant extraction),
+    /// kept so items can be re-categorized without re-parsing; empty for items
+    /// saved before it existed, which fall back to `title`
+    #[wasm_bindgen(skip)]
+    #[serde(default)]
+    pub source_title: String,
+    /// Album name from an `#EXTALB:` line
+    #[wasm_bindgen(skip)]
+    pub album: Option<String>,
+    /// `#EXTVLCOPT:key=value` player options
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub vlc_options: HashMap<String, String>,
+    /// `#KODIPROP:key=value` properties (inputstream/DRM settings)
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub kodi_props: HashMap<String, String>,
+    /// Extra art attributes (`poster`, `backdrop`, `tvg-logo-small`, ...) by name;
+    /// `logo` stays the primary image
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub artwork: HashMap<String, String>,
+    /// Recording support from `tvg-rec` (`None` when the feed doesn't say)
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recordable: Option<bool>,
+    /// Remaining `#EXTINF` attributes (`tvg-id`, `tvg-name`, ...), keys lowercased;
+    /// `tvg-logo` and `group-title` live in `logo` and `group`
+    #[wasm_bindgen(skip)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub attributes: HashMap<String, String>,
+}
+
+impl M3UItem {
+    /// Re-run categorization on `source_title` (or `title` when it's empty)
+    /// with the given policy
+    pub fn recategorize(&mut self, policy: &CategorizationPolicy) {
+        if self.source_title.is_empty() {
+            self.source_title = self.title.clone();
+        }
+        let categorized = categorizer::categorize_entry(
+            &self.source_title,
+            &self.url,
+            &self.group,
+            self.duration,
+            policy,
+            categorizer::default_pipeline(),
+        );
+        self.apply_categorized(categorized);
+    }
+
+    /// Normalized title for grouping variants of the same content
+    ///
+    /// Builds on the categorizer's cleanup (year, bracketed quality, audio
+    /// variant) and also drops bare quality tokens, language tags and
+    /// separators: `Movie (2020) 1080p` and `Movie 2020 [TR]` both give "movie".
+    pub fn base_title(&self) -> String {
+        normalizer::base_title(&self.title)
+    }
+
+    /// Zero-padded episode code (`S01E05`, `S01E01-E02`), `None` unless both
+    /// season and episode are known
+    pub fn format_episode(&self) -> Option<String> {
+        let (season, episode) = (self.season?, self.episode?);
+        let mut code = format!("S{:02}E{:02}", season, episode);
+        if let Some(end) = self.episode_end {
+            code.push_str(&format!("-E{:02}", end));
+        }
+        Some(code)
+    }
+
+    /// HTTP headers players should send for this stream
+    ///
+    /// Derived from `#EXTVLCOPT:http-*` options and the
+    /// `inputstream.adaptive.stream_headers` Kodi property (`A=1&B=2`).
+    pub fn playback_headers(&self) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+
+        if let Some(stream_headers) = self.kodi_props.get("inputstream.adaptive.stream_headers") {
+            for pair in stream_headers.split('&') {
+                if let Some((name, value)) = pair.split_once('=') {
+                    headers.insert(name.trim().to_string(), value.trim().to_string());
+                }
+            }
+        }
+
+        for (option, header) in [
+            ("http-user-agent", "User-Agent"),
+            ("http-referrer", "Referer"),
+            ("http-referer", "Referer"),
+            ("http-origin", "Origin"),
+            ("http-cookie", "Cookie"),
+        ] {
+            if let Some(value) = self.vlc_options.get(option) {
+                headers.insert(header.to_string(), value.clone());
+            }
+        }
+
+        headers
+    }
+
+    /// Copy category and extracted metadata onto the item
+    pub(crate) fn apply_categorized(&mut self, categorized: CategorizedItem) {
+        // Bare episode markers ("S01E05") leave no series name: use the group instead
+        self.title = if !categorized.cleaned_title.is_empty() {
+            categorized.cleaned_title
+        } else if !self.group.is_empty() {
+            self.group.to_string()
+        } else {
+            self.source_title.clone()
+        };
+        self.category = categorized.category;
+        self.year = categorized.year;
+        self.season = categorized.season;
+        self.season_end = categorized.season_end;
+        self.episode = categorized.episode;
+        self.episode_end = categorized.episode_end;
+        self.is_finale = categorized.is_finale;
+        self.is_complete = categorized.is_complete;
+        self.audio_variant = categorized.audio_variant;
+        self.quality = categorized.quality;
+        self.country = categorized.country;
+    }
+}
+
+/// Re-categorize one item after its title was edited
+///
+/// The new title becomes the item's `source_title`; category and title
+/// metadata are re-detected with the default policy. Group, URL and other
+/// playlist attributes are left as they are.
+pub fn recategorize_item(item: &mut M3UItem, new_title: &str) {
+    item.source_title = new_title.to_string();
+    item.recategorize(&CategorizationPolicy::default());
 }
 
 #[wasm_bindgen]
@@ -47,7 +268,7 @@ impl M3UItem {
 
     #[wasm_bindgen(getter)]
     pub fn group(&self) -> String {
-        self.group.clone()
+        self.group.to_string()
     }
 
     #[wasm_bindgen(getter)]
@@ -65,23 +286,178 @@ impl M3UItem {
         self.season
     }
 
+    #[wasm_bindgen(getter)]
+    pub fn season_end(&self) -> Option<u32> {
+        self.season_end
+    }
+
     #[wasm_bindgen(getter)]
     pub fn episode(&self) -> Option<u32> {
         self.episode
     }
+
+    #[wasm_bindgen(getter)]
+    pub fn episode_end(&self) -> Option<u32> {
+        self.episode_end
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn is_finale(&self) -> bool {
+        self.is_finale
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn is_complete(&self) -> bool {
+        self.is_complete
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn audio_variant(&self) -> Option<String> {
+        self.audio_variant.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn quality(&self) -> Option<String> {
+        self.quality.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn country(&self) -> Option<String> {
+        self.country.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn duration(&self) -> Option<f64> {
+        self.duration
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn recordable(&self) -> Option<bool> {
+        self.recordable
+    }
+
+    /// Normalized title for grouping variants (see `base_title`)
+    #[wasm_bindgen(js_name = baseTitle)]
+    pub fn base_title_js(&self) -> String {
+        self.base_title()
+    }
+
+    /// Change the title and re-detect category and metadata from it
+    #[wasm_bindgen(js_name = recategorizeWithTitle)]
+    pub fn recategorize_with_title_js(&mut self, new_title: &str) {
+        recategorize_item(self, new_title);
+    }
+
+    /// Whether the stream is served over HTTPS
+    #[wasm_bindgen(js_name = isSecure)]
+    pub fn is_secure(&self) -> bool {
+        self.url
+            .trim_start()
+            .get(..8)
+            .is_some_and(|scheme| scheme.eq_ignore_ascii_case("https://"))
+    }
 }
 
 /// Parse M3U content and return categorized items
+///
+/// Errors are `{ code, message, line }` objects (see `ParseError`).
 #[wasm_bindgen]
 pub fn parse_m3u(content: &str) -> Result<JsValue, JsValue> {
-    let parser = M3UParser::new(content);
-    match parser.parse() {
-        Ok(items) => {
-            serde_wasm_bindgen::to_value(&items)
-                .map_err(|e| JsValue::from_str(&format!("Serialization error: {}", e)))
-        }
-        Err(e) => Err(JsValue::from_str(&e)),
-    }
+    let items = M3UParser::new(content).parse()?;
+    Ok(serde_wasm_bindgen::to_value(&items).map_err(ParseError::serialization)?)
+}
+
+/// Parse M3U content with parser options (e.g. `{ strip_prefix: "VIP | " }`)
+#[wasm_bindgen]
+pub fn parse_m3u_with_options(content: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let options: ParserOptions = serde_wasm_bindgen::from_value(options)
+        .map_err(|e| ParseError::invalid_input("parser options", e))?;
+    let items = M3UParser::with_options(content, options).parse()?;
+    Ok(serde_wasm_bindgen::to_value(&items).map_err(ParseError::serialization)?)
+}
+
+/// Parse M3U content with parser options, returning `{ items, warnings }`
+#[wasm_bindgen]
+pub fn parse_m3u_detailed(content: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let options: ParserOptions = serde_wasm_bindgen::from_value(options)
+        .map_err(|e| ParseError::invalid_input("parser options", e))?;
+    let result = M3UParser::with_options(content, options).parse_detailed()?;
+    Ok(serde_wasm_bindgen::to_value(&result).map_err(ParseError::serialization)?)
+}
+
+/// Parse M3U content returning `{ items, metrics: { items, bytes, micros } }`
+#[wasm_bindgen]
+pub fn parse_m3u_with_metrics(content: &str) -> Result<JsValue, JsValue> {
+    let result = parse_with_metrics(content)?;
+    Ok(serde_wasm_bindgen::to_value(&result).map_err(ParseError::serialization)?)
+}
+
+/// Upper-bound item count from a cheap `#EXTINF` scan (no parsing)
+#[wasm_bindgen(js_name = estimate_items)]
+pub fn estimate_items_js(content: &str) -> usize {
+    estimate_items(content)
+}
+
+/// Parse M3U content straight into a `CategoryTree`
+///
+/// Avoids the round trip of the item list through JS that `parse_m3u` +
+/// `CategoryTree.build` needs.
+#[wasm_bindgen]
+pub fn build_tree_from_m3u(content: &str) -> Result<CategoryTree, JsValue> {
+    Ok(CategoryTree::from_m3u(content)?)
+}
+
+/// Parse raw playlist bytes in the given encoding (`"windows-1254"`, `"latin1"`, ...)
+///
+/// Encoding labels follow the WHATWG Encoding Standard; an empty label means
+/// UTF-8. A byte order mark overrides the label.
+pub fn parse_bytes_with_encoding(
+    bytes: &[u8],
+    encoding: &str,
+) -> Result<Vec<M3UItem>, ParseError> {
+    let encoding = if encoding.trim().is_empty() {
+        encoding_rs::UTF_8
+    } else {
+        encoding_rs::Encoding::for_label(encoding.trim().as_bytes()).ok_or_else(|| {
+            ParseError::new(
+                ErrorCode::UnsupportedEncoding,
+                format!("Unsupported encoding: {}", encoding),
+            )
+        })?
+    };
+    let (content, _, _) = encoding.decode(bytes);
+    M3UParser::new(&content).parse()
+}
+
+/// Parse M3U bytes in a non-UTF-8 encoding (defaults to UTF-8)
+#[wasm_bindgen]
+pub fn parse_m3u_bytes(bytes: &[u8], encoding: Option<String>) -> Result<JsValue, JsValue> {
+    let items = parse_bytes_with_encoding(bytes, encoding.as_deref().unwrap_or_default())?;
+    Ok(serde_wasm_bindgen::to_value(&items).map_err(ParseError::serialization)?)
+}
+
+/// Compare two parsed playlists, returning `{ added, removed, changed }`
+#[wasm_bindgen]
+pub fn diff_playlists(old: JsValue, new: JsValue) -> Result<JsValue, JsValue> {
+    let old: Vec<M3UItem> = serde_wasm_bindgen::from_value(old)
+        .map_err(|e| ParseError::invalid_input("items", e))?;
+    let new: Vec<M3UItem> = serde_wasm_bindgen::from_value(new)
+        .map_err(|e| ParseError::invalid_input("items", e))?;
+    Ok(serde_wasm_bindgen::to_value(&diff(&old, &new)).map_err(ParseError::serialization)?)
+}
+
+/// Categorize a single title/URL pair, returning the `CategorizedItem` as JSON
+///
+/// Debugging helper for the classifier: `category` holds the category name.
+#[wasm_bindgen]
+pub fn categorize(title: &str, url: &str) -> String {
+    serde_json::to_string(&categorize_item(title, url)).unwrap_or_default()
+}
+
+/// Year/episode/quality marker flags of a raw title (`{ has_year, ... }`)
+#[wasm_bindgen(js_name = title_markers)]
+pub fn title_markers_js(title: &str) -> Result<JsValue, JsValue> {
+    Ok(serde_wasm_bindgen::to_value(&title_markers(title)).map_err(ParseError::serialization)?)
 }
 
 /// Get version information
@@ -94,6 +470,56 @@ pub fn version() -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_categorize_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&categorize("Show S01E01", "http://x/y.mkv")).unwrap();
+        assert_eq!(json["category"], "Series");
+        assert_eq!(json["cleaned_title"], "Show");
+        assert_eq!(json["season"], 1);
+        assert_eq!(json["episode"], 1);
+    }
+
+    #[test]
+    fn test_format_episode() {
+        let mut item = M3UItem {
+            season: Some(1),
+            episode: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(item.format_episode(), Some("S01E05".to_string()));
+
+        item.episode_end = Some(6);
+        assert_eq!(item.format_episode(), Some("S01E05-E06".to_string()));
+
+        item.season = Some(12);
+        item.episode = Some(104);
+        item.episode_end = None;
+        assert_eq!(item.format_episode(), Some("S12E104".to_string()));
+
+        item.episode = None;
+        assert_eq!(item.format_episode(), None);
+    }
+
+    #[test]
+    fn test_playback_headers() {
+        let content = r#"#EXTM3U
+#EXTINF:-1,Channel
+#EXTVLCOPT:http-user-agent=ZenithTV/1.0
+#EXTVLCOPT:http-referrer=http://example.com/
+#KODIPROP:inputstream.adaptive.stream_headers=Origin=http://example.com&X-Token=abc
+http://example.com/live/channel
+"#;
+        let items = M3UParser::new(content).parse().unwrap();
+        let headers = items[0].playback_headers();
+
+        assert_eq!(headers.len(), 4);
+        assert_eq!(headers["User-Agent"], "ZenithTV/1.0");
+        assert_eq!(headers["Referer"], "http://example.com/");
+        assert_eq!(headers["Origin"], "http://example.com");
+        assert_eq!(headers["X-Token"], "abc");
+    }
+
     #[test]
     fn test_basic_parsing() {
         let content = r#"#EXTM3U
@@ -146,4 +572,106 @@ http://example.com/show.mkv
         assert_eq!(items[0].episode, Some(5));
         assert_eq!(items[0].category, Category::Series);
     }
+
+    #[test]
+    fn test_parse_bytes_windows_1254() {
+        // "Şahin Gülşen" in Windows-1254: Ş = 0xDE, ü = 0xFC, ş = 0xFE
+        let mut bytes = b"#EXTM3U\n#EXTINF:-1,".to_vec();
+        bytes.extend_from_slice(&[0xDE, b'a', b'h', b'i', b'n', b' ', b'G', 0xFC, b'l', 0xFE]);
+        bytes.extend_from_slice(b"en\nhttp://example.com/movie.mkv\n");
+
+        let items = parse_bytes_with_encoding(&bytes, "windows-1254").unwrap();
+        assert_eq!(items[0].title, "Şahin Gülşen");
+
+        // Plain UTF-8 is the default
+        let utf8 = "#EXTM3U\n#EXTINF:-1,Gülşen\nhttp://example.com/movie.mkv\n";
+        let items = parse_bytes_with_encoding(utf8.as_bytes(), "").unwrap();
+        assert_eq!(items[0].title, "Gülşen");
+
+        assert!(parse_bytes_with_encoding(&bytes, "klingon").is_err());
+    }
+
+    #[test]
+    fn test_base_title_groups_variants() {
+        let content = "#EXTM3U\n\
+            #EXTINF:-1,Movie (2020) 1080p\nhttp://example.com/a.mkv\n\
+            #EXTINF:-1,Movie 2020 [TR]\nhttp://example.com/b.mkv\n\
+            #EXTINF:-1,Movie (Latino) [HD]\nhttp://example.com/c.mkv\n\
+            #EXTINF:-1,The Office (US)\nhttp://example.com/d.mkv\n";
+        let items = M3UParser::new(content).parse().unwrap();
+
+        assert_eq!(items[0].base_title(), "movie");
+        assert_eq!(items[1].base_title(), items[0].base_title());
+        assert_eq!(items[2].base_title(), items[0].base_title());
+        assert_eq!(items[3].base_title(), "the office (us)");
+    }
+
+    #[test]
+    fn test_recategorize_item() {
+        let content = "#EXTM3U\n\
+            #EXTINF:-1 group-title=\"Mixed\",Some Movie (2020)\nhttp://example.com/a.mkv\n";
+        let mut item = M3UParser::new(content).parse().unwrap().remove(0);
+        assert_eq!(item.category, Category::Movie);
+        assert_eq!(item.year, Some(2020));
+
+        recategorize_item(&mut item, "Some Show S01E01");
+        assert_eq!(item.category, Category::Series);
+        assert_eq!(item.title, "Some Show");
+        assert_eq!((item.season, item.episode), (Some(1), Some(1)));
+        assert_eq!(item.year, None);
+        assert_eq!(item.source_title, "Some Show S01E01");
+        assert_eq!(&*item.group, "Mixed");
+    }
+
+    #[test]
+    fn test_item_without_source_title() {
+        let json = r#"{"title":"Old Show S01E02","url":"http://example.com/a.mkv",
+            "group":"Saved","logo":null,"category":"Movie"}"#;
+        let mut item: M3UItem = serde_json::from_str(json).unwrap();
+        assert_eq!(item.source_title, "");
+        assert!(!item.is_finale);
+        assert!(to_m3u([&item]).contains(",Old Show S01E02\n"));
+
+        item.recategorize(&CategorizationPolicy::default());
+        assert_eq!(item.category, Category::Series);
+        assert_eq!(item.title, "Old Show");
+        assert_eq!(item.source_title, "Old Show S01E02");
+    }
+
+    #[test]
+    fn test_recategorize_keeps_year_in_title() {
+        let policy = CategorizationPolicy {
+            strip_year_from_title: false,
+            ..Default::default()
+        };
+        let mut item = M3UItem {
+            source_title: "Great Movie (2022)".to_string(),
+            url: "http://example.com/movie.mkv".to_string(),
+            ..Default::default()
+        };
+        item.recategorize(&policy);
+        assert_eq!(item.title, "Great Movie (2022)");
+        assert_eq!(item.year, Some(2022));
+
+        let tree = CategoryTree::build(vec![item]).rebuild_with(&policy);
+        assert_eq!(tree.items().next().unwrap().title, "Great Movie (2022)");
+
+        let tree = tree.rebuild_with(&CategorizationPolicy::default());
+        assert_eq!(tree.items().next().unwrap().title, "Great Movie");
+    }
+
+    #[test]
+    fn test_is_secure() {
+        let mut item = M3UItem {
+            url: "https://example.com/live/1".to_string(),
+            ..Default::default()
+        };
+        assert!(item.is_secure());
+        item.url = "HTTPS://example.com/live/1".to_string();
+        assert!(item.is_secure());
+        item.url = "http://example.com/live/1".to_string();
+        assert!(!item.is_secure());
+        item.url = "rtmp://example.com/live".to_string();
+        assert!(!item.is_secure());
+    }
 }
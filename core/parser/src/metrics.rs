@@ -0,0 +1,128 @@
+use serde::Serialize;
+use crate::{M3UItem, M3UParser, ParseError};
+use clock::Timer;
+
+/// Size and timing of a single parse, for reporting slow feeds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ParseMetrics {
+    /// Number of items produced
+    pub items: usize,
+    /// Input size in bytes
+    pub bytes: usize,
+    /// Wall-clock parse time in microseconds
+    pub micros: u64,
+}
+
+/// Items together with the metrics of the parse that produced them
+#[derive(Debug, Clone, Serialize)]
+pub struct MeasuredParse {
+    pub items: Vec<M3UItem>,
+    pub metrics: ParseMetrics,
+}
+
+/// Parse M3U content, measuring the time spent
+///
+/// Uses `performance.now()` on wasm and `std::time::Instant` natively.
+pub fn parse_with_metrics(content: &str) -> Result<MeasuredParse, ParseError> {
+    let timer = Timer::start();
+    let items = M3UParser::new(content).parse()?;
+    let micros = timer.elapsed_micros();
+
+    Ok(MeasuredParse {
+        metrics: ParseMetrics {
+            items: items.len(),
+            bytes: content.len(),
+            micros,
+        },
+        items,
+    })
+}
+
+/// Predict the item count of a playlist without parsing it
+///
+/// Counts `#EXTINF` markers in one `memchr` scan; entries without a URL or
+/// dropped by parser options make it an upper bound. Useful for deciding on
+/// chunked or parallel parsing before allocating anything.
+pub fn estimate_items(content: &str) -> usize {
+    memchr::memmem::find_iter(content.as_bytes(), b"#EXTINF").count()
+}
+
+#[cfg(target_arch = "wasm32")]
+mod clock {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = performance, js_name = now)]
+        fn performance_now() -> f64;
+    }
+
+    /// Milliseconds from `performance.now()`
+    pub struct Timer(f64);
+
+    impl Timer {
+        pub fn start() -> Self {
+            Self(performance_now())
+        }
+
+        pub fn elapsed_micros(&self) -> u64 {
+            ((performance_now() - self.0) * 1000.0).max(0.0) as u64
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod clock {
+    use std::time::Instant;
+
+    pub struct Timer(Instant);
+
+    impl Timer {
+        pub fn start() -> Self {
+            Self(Instant::now())
+        }
+
+        pub fn elapsed_micros(&self) -> u64 {
+            self.0.elapsed().as_micros() as u64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_with_metrics() {
+        let mut content = String::from("#EXTM3U\n");
+        for i in 0..200 {
+            content.push_str(&format!("#EXTINF:-1,Movie {}\nhttp://example.com/{}.mkv\n", i, i));
+        }
+
+        let result = parse_with_metrics(&content).unwrap();
+        assert_eq!(result.items.len(), 200);
+        assert_eq!(result.metrics.items, 200);
+        // `micros` can round to 0 on a fast machine, so only the counts are checked
+        assert_eq!(result.metrics.bytes, content.len());
+
+        assert!(parse_with_metrics("nope").is_err());
+    }
+
+    #[test]
+    fn test_estimate_items() {
+        let mut content = String::from("#EXTM3U\n#PLAYLIST:Sample\n");
+        for i in 0..500 {
+            if i % 10 == 0 {
+                content.push_str("#EXTVLCOPT:http-user-agent=Zenith\n# comment\n\n");
+            }
+            content.push_str(&format!("#EXTINF:-1,Show {}\nhttp://example.com/{}.mkv\n", i, i));
+        }
+        content.push_str("#EXTINF:-1,Dangling\n");
+
+        let actual = M3UParser::new(&content).parse().unwrap().len();
+        let estimate = estimate_items(&content);
+        assert_eq!(actual, 500);
+        assert!(estimate >= actual && estimate - actual <= actual / 100);
+        assert_eq!(estimate_items(""), 0);
+    }
+}
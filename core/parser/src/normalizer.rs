@@ -0,0 +1,281 @@
+use std::borrow::Cow;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    /// Release noise left in cleaned titles that doesn't identify the content
+    /// Matches: 1080p, HD, 4K (bare or bracketed), [TR], (ENG), [Multi]
+    static ref BASE_TITLE_NOISE: Regex = Regex::new(concat!(
+        r"(?i)[(\[]?\b(?:sd|hd|fhd|uhd|4k|8k|480p|720p|1080p|2160p)\b[)\]]?",
+        r"|[(\[]\s*(?:tr|en|eng|de|ger|fr|es|spa|it|ru|ar|pt|nl|pl|multi|dual)\s*[)\]]",
+    )).unwrap();
+}
+
+/// Replace `.` and `_` word separators with spaces and collapse whitespace
+///
+/// Examples:
+/// - "The.Show.S01E01" -> "The Show S01E01"
+/// - "Some_Movie__2020" -> "Some Movie 2020"
+pub fn normalize_separators(title: &str) -> String {
+    title
+        .replace(['.', '_'], " ")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Normalize typographic characters that break search and sorting
+///
+/// Smart quotes become ASCII quotes, non-breaking spaces become regular
+/// spaces, zero-width and control characters are removed. Letters from any
+/// script are left untouched. Borrows when there is nothing to change.
+pub fn normalize_unicode(title: &str) -> Cow<'_, str> {
+    if title.chars().all(|ch| ch.is_ascii() && !ch.is_ascii_control()) {
+        return Cow::Borrowed(title);
+    }
+
+    let normalized: String = title
+        .chars()
+        .filter_map(|ch| match ch {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => Some('\''),
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' => Some('"'),
+            '\u{00A0}' | '\u{2007}' | '\u{202F}' => Some(' '),
+            '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' => None,
+            ch if ch.is_control() => ch.is_whitespace().then_some(' '),
+            ch => Some(ch),
+        })
+        .collect();
+
+    Cow::Owned(normalized)
+}
+
+/// Remove a leading run of emoji and other non-ASCII symbols (`🔴`, `⭐`, `▶️`)
+/// and the whitespace after it
+///
+/// Letters and digits of any script, ASCII punctuation and flag emoji (kept
+/// for country detection) stop the run.
+///
+/// Examples:
+/// - "🔴 Live News" -> "Live News"
+/// - "⭐⭐ VIP Movie" -> "VIP Movie"
+pub fn strip_leading_symbols(title: &str) -> &str {
+    let is_flag = |c: char| ('\u{1F1E6}'..='\u{1F1FF}').contains(&c);
+    let start = title
+        .char_indices()
+        .find(|&(_, c)| {
+            !c.is_whitespace() && (c.is_ascii() || c.is_alphanumeric() || is_flag(c))
+        })
+        .map_or(title.len(), |(index, _)| index);
+    &title[start..]
+}
+
+/// Strip separator punctuation (`-`, `|`, `:`, ...) and whitespace dangling at
+/// either end of a cleaned title
+///
+/// Examples:
+/// - "Show Name -" -> "Show Name"
+/// - "| Show Name" -> "Show Name"
+pub fn trim_separators(title: &str) -> &str {
+    title.trim_matches(|c: char| c.is_whitespace() || "-|:–—·/,".contains(c))
+}
+
+/// Split a multi-group value (`News;Sports`, `News,Sports`) into trimmed,
+/// non-empty group names
+///
+/// A backslash escapes the next character, so `News\;Sports` is the single
+/// group `News;Sports` and `\\` is a literal backslash.
+///
+/// Examples:
+/// - "News;Sports" -> ["News", "Sports"]
+/// - "News\;Sports, Kids" -> ["News;Sports", "Kids"]
+pub fn split_group_list(value: &str) -> Vec<String> {
+    let mut groups = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => current.push(chars.next().unwrap_or('\\')),
+            ';' | ',' => groups.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    groups.push(current);
+
+    groups
+        .into_iter()
+        .map(|group| group.trim().to_string())
+        .filter(|group| !group.is_empty())
+        .collect()
+}
+
+/// Lowercased title with quality/language tags and separators removed, so
+/// variants of one release compare equal (`Movie 1080p`, `Movie [TR]` -> "movie")
+///
+/// Expects a cleaned title (year and audio variant already extracted).
+pub(crate) fn base_title(title: &str) -> String {
+    let stripped = BASE_TITLE_NOISE.replace_all(title, " ");
+    let normalized = normalize_separators(&stripped);
+    trim_separators(&normalized).to_lowercase()
+}
+
+/// Repair common URL defects
+///
+/// Trims surrounding whitespace, percent-encodes interior spaces and adds
+/// `http:` to scheme-relative URLs (`//host/stream`). URLs without a scheme
+/// can't be repaired and are returned as an error. Borrows when nothing changed.
+///
+/// Examples:
+/// - " http://host/my stream.ts " -> "http://host/my%20stream.ts"
+/// - "//host/live/1" -> "http://host/live/1"
+pub fn normalize_url(url: &str) -> Result<Cow<'_, str>, String> {
+    let trimmed = url.trim();
+    if trimmed.is_empty() {
+        return Err("empty URL".to_string());
+    }
+
+    let mut repaired = encode_url_spaces(trimmed);
+
+    if repaired.starts_with("//") {
+        repaired = Cow::Owned(format!("http:{}", repaired));
+    } else if !has_scheme(&repaired) {
+        return Err(format!("URL has no scheme: {}", trimmed));
+    }
+
+    Ok(repaired)
+}
+
+/// Trim a URL and percent-encode interior whitespace, each run becoming a
+/// single `%20`; borrows when the URL had none
+///
+/// Examples:
+/// - "http://host/movie.mkv " -> "http://host/movie.mkv"
+/// - "http://host/my  movie.mkv" -> "http://host/my%20movie.mkv"
+pub fn encode_url_spaces(url: &str) -> Cow<'_, str> {
+    let trimmed = url.trim();
+    if trimmed.contains(char::is_whitespace) {
+        Cow::Owned(trimmed.split_whitespace().collect::<Vec<&str>>().join("%20"))
+    } else {
+        Cow::Borrowed(trimmed)
+    }
+}
+
+/// `scheme:` prefix per RFC 3986 (letter, then letters/digits/`+`/`-`/`.`)
+fn has_scheme(url: &str) -> bool {
+    let Some((scheme, _)) = url.split_once(':') else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|ch| ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || "+-.".contains(ch))
+}
+
+/// Last path segment of a URL, without query string, fragment or trailing slashes
+///
+/// Examples:
+/// - "http://host/movies/film.mkv?token=1" -> "film.mkv"
+/// - "http://host/series/show/" -> "show"
+pub fn url_filename(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let path = path.trim_end_matches('/');
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Readable title from the URL's last path segment (query and extension removed)
+pub(crate) fn title_from_url(url: &str) -> &str {
+    let filename = url_filename(url);
+    let stem = match filename.rfind('.') {
+        Some(dot) if dot > 0 => &filename[..dot],
+        _ => filename,
+    };
+
+    if stem.is_empty() {
+        url
+    } else {
+        stem
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_filename() {
+        assert_eq!(url_filename("http://host/movies/film.mkv?token=abc/def"), "film.mkv");
+        assert_eq!(url_filename("http://host/series/show/"), "show");
+        assert_eq!(url_filename("http://host/live/channel.ts"), "channel.ts");
+        assert_eq!(url_filename("film.mkv"), "film.mkv");
+    }
+
+    #[test]
+    fn test_dotted_title() {
+        assert_eq!(normalize_separators("The.Movie.Name"), "The Movie Name");
+    }
+
+    #[test]
+    fn test_underscored_title() {
+        assert_eq!(normalize_separators("Some_Movie__2020"), "Some Movie 2020");
+    }
+
+    #[test]
+    fn test_collapses_whitespace() {
+        assert_eq!(normalize_separators("  Spaced   Out  Title "), "Spaced Out Title");
+    }
+
+    #[test]
+    fn test_normalize_unicode() {
+        assert_eq!(normalize_unicode("Movie\u{00A0}Name\u{200B}"), "Movie Name");
+        assert_eq!(normalize_unicode("\u{201C}Quoted\u{201D} It\u{2019}s"), "\"Quoted\" It's");
+        assert_eq!(normalize_unicode("Tab\tand\u{0007}bell"), "Tab andbell");
+        assert_eq!(normalize_unicode("Türkçe Dizi Şahane"), "Türkçe Dizi Şahane");
+        assert!(matches!(normalize_unicode("Plain Title"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(
+            normalize_url(" http://host/my stream.ts ").unwrap(),
+            "http://host/my%20stream.ts"
+        );
+        assert_eq!(normalize_url("//host/live/1").unwrap(), "http://host/live/1");
+        assert!(matches!(normalize_url("rtmp://host/app").unwrap(), Cow::Borrowed(_)));
+        assert!(normalize_url("host/live/1").is_err());
+        assert!(normalize_url("   ").is_err());
+    }
+
+    #[test]
+    fn test_encode_url_spaces() {
+        assert_eq!(encode_url_spaces("http://host/movie.mkv \t"), "http://host/movie.mkv");
+        assert_eq!(encode_url_spaces("http://host/my  movie.mkv"), "http://host/my%20movie.mkv");
+        let valid = "http://host/my%20movie.mkv?token=a+b";
+        assert!(matches!(encode_url_spaces(valid), Cow::Borrowed(url) if url == valid));
+    }
+
+    #[test]
+    fn test_strip_leading_symbols() {
+        assert_eq!(strip_leading_symbols("🔴 Live News"), "Live News");
+        assert_eq!(strip_leading_symbols("⭐ VIP Movie"), "VIP Movie");
+        assert_eq!(strip_leading_symbols("▶️★ Çocuk"), "Çocuk");
+        assert_eq!(strip_leading_symbols("🇹🇷 TRT 1"), "🇹🇷 TRT 1");
+        assert_eq!(strip_leading_symbols("[VIP] Movie ⭐"), "[VIP] Movie ⭐");
+    }
+
+    #[test]
+    fn test_split_group_list() {
+        assert_eq!(split_group_list("News;Sports"), ["News", "Sports"]);
+        assert_eq!(split_group_list(r"News\;Sports"), ["News;Sports"]);
+        let groups = split_group_list(r"News\;Sports; Kids ,Docs\,Nature");
+        assert_eq!(groups, ["News;Sports", "Kids", "Docs,Nature"]);
+        assert_eq!(split_group_list(r"A\\;B"), [r"A\", "B"]);
+        assert!(split_group_list(" ; ").is_empty());
+    }
+
+    #[test]
+    fn test_trim_separators() {
+        assert_eq!(trim_separators("Show Name -"), "Show Name");
+        assert_eq!(trim_separators("| Show Name"), "Show Name");
+        assert_eq!(trim_separators("Mission: Impossible"), "Mission: Impossible");
+        assert_eq!(trim_separators(" - "), "");
+    }
+}
@@ -0,0 +1,130 @@
+use rayon::prelude::*;
+
+use crate::{M3UItem, M3UParser, ParseError};
+
+/// Chunks smaller than this aren't worth a rayon task
+const MIN_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Parse a large playlist on all cores, returning the same items in the same
+/// order as `M3UParser::parse`
+///
+/// The body is split at entry boundaries and the chunks are parsed
+/// independently, so warnings and playlist metadata aren't collected.
+pub fn parse_parallel(content: &str) -> Result<Vec<M3UItem>, ParseError> {
+    let chunks = (content.len() / MIN_CHUNK_BYTES).clamp(1, rayon::current_num_threads() * 4);
+    parse_in_chunks(content, chunks)
+}
+
+fn parse_in_chunks(content: &str, chunks: usize) -> Result<Vec<M3UItem>, ParseError> {
+    let mut header = M3UParser::new(content);
+    if !header.read_header()? {
+        return Err(ParseError::missing_header());
+    }
+
+    let body = &content[header.cursor..];
+    let items = split_entries(body, chunks)
+        .into_par_iter()
+        .map(|chunk| {
+            let mut parser = M3UParser::new(chunk);
+            let mut items = Vec::new();
+            while let Some((metadata_line, directives, url_line)) = parser.read_entry() {
+                if let Some(item) = parser.parse_entry(metadata_line, &directives, url_line) {
+                    items.push(item);
+                }
+            }
+            items
+        })
+        .collect::<Vec<_>>()
+        .concat();
+
+    Ok(items)
+}
+
+/// Split `body` into about `chunks` slices that each start on a fresh entry
+///
+/// A cut is placed after the URL line of the first `#EXTINF` found past the
+/// target offset: any directives before the next `#EXTINF` belong to the next
+/// entry, so the sequential parser would start from a clean state there too.
+fn split_entries(body: &str, chunks: usize) -> Vec<&str> {
+    let target = body.len() / chunks.max(1);
+    let mut slices = Vec::with_capacity(chunks);
+    let mut start = 0;
+
+    while slices.len() + 1 < chunks {
+        let Some(cut) = entry_end_after(body, start + target) else {
+            break;
+        };
+        slices.push(&body[start..cut]);
+        start = cut;
+    }
+
+    slices.push(&body[start..]);
+    slices
+}
+
+/// Offset just past the URL line of the first `#EXTINF` starting at or after `from`
+fn entry_end_after(body: &str, from: usize) -> Option<usize> {
+    // Move to the start of a line; byte search since `from` may split a character
+    let bytes = body.as_bytes();
+    let mut offset = from;
+    if offset >= bytes.len() {
+        return None;
+    }
+    if offset > 0 && bytes[offset - 1] != b'\n' {
+        offset += memchr::memchr(b'\n', &bytes[offset..])? + 1;
+    }
+
+    let mut in_entry = false;
+    for line in body[offset..].split_inclusive('\n') {
+        offset += line.len();
+        let trimmed = line.trim();
+        if trimmed.starts_with("#EXTINF") {
+            in_entry = true;
+        } else if in_entry && !trimmed.is_empty() && !trimmed.starts_with('#') {
+            return (offset < body.len()).then_some(offset);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playlist(entries: usize) -> String {
+        let mut content = String::from("#EXTM3U\n#PLAYLIST:Big\n");
+        for i in 0..entries {
+            if i % 7 == 0 {
+                content.push_str("#EXTVLCOPT:http-user-agent=Zenith\n");
+            }
+            content.push_str(&format!(
+                "#EXTINF:-1 group-title=\"Group {}\",Show {} S01E{:02}\n",
+                i % 5,
+                i / 50,
+                i % 50 + 1,
+            ));
+            if i % 11 == 0 {
+                content.push_str("# comment\n\n");
+            }
+            content.push_str(&format!("http://example.com/{}.mkv\r\n", i));
+        }
+        content
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let content = playlist(500);
+        let sequential = M3UParser::new(&content).parse().unwrap();
+
+        for chunks in [1, 2, 3, 16, 1000] {
+            let parallel = parse_in_chunks(&content, chunks).unwrap();
+            assert_eq!(parallel, sequential, "{} chunks", chunks);
+        }
+        assert_eq!(parse_parallel(&content).unwrap().len(), 500);
+
+        assert!(split_entries(&content, 16).len() > 1);
+        let error = parse_parallel("http://example.com\n").unwrap_err();
+        assert_eq!(error.code, crate::ErrorCode::MissingHeader);
+    }
+}
@@ -1,57 +1,257 @@
-use crate::{categorize_item, M3UItem};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use url::Url;
+use serde::{Deserialize, Serialize};
+use crate::{CategorizationPolicy, DetectorPipeline, M3UItem, normalize_separators, ParseError};
+use crate::categorizer::{categorize_entry, default_pipeline};
+use crate::normalizer::{
+    encode_url_spaces, normalize_unicode, normalize_url, strip_leading_symbols, title_from_url,
+};
+
+/// Default cap for a single attribute value (64 KB)
+const DEFAULT_MAX_ATTRIBUTE_LENGTH: usize = 64 * 1024;
+
+/// Options controlling how entries are cleaned during parsing
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ParserOptions {
+    /// Constant provider prefix removed from every title (e.g. `VIP | `, `|US|`)
+    pub strip_prefix: Option<String>,
+    /// Replace `.`/`_` separators with spaces before detection (`The.Show.S01E01`)
+    pub normalize_separators: bool,
+    /// Classification precedence passed to the categorizer
+    pub policy: CategorizationPolicy,
+    /// Attribute values longer than this (in bytes) are truncated with a warning
+    pub max_attribute_length: usize,
+    /// Stop parsing after this many items (`ParseResult::truncated` is set if more remain)
+    pub max_items: Option<usize>,
+    /// Drop fake section entries (`###### MOVIES ######`, dummy URLs) with a warning
+    pub drop_separators: bool,
+    /// Repair URLs with `normalize_url` (unfixable ones are kept and warned about)
+    pub normalize_urls: bool,
+    /// Percent-encode whitespace inside URLs (`my movie.mkv` -> `my%20movie.mkv`)
+    /// without the other `normalize_urls` repairs; surrounding whitespace is
+    /// always trimmed
+    pub encode_url_spaces: bool,
+    /// Playlist URL that relative entry URLs (`segment.ts`) are resolved against
+    pub base_url: Option<String>,
+    /// Expand a season-range entry (`Show Complete S01-S03`) into one item per
+    /// season sharing the URL, for UIs that list seasons as rows
+    pub expand_season_ranges: bool,
+    /// Remove leading emoji/symbol decorations (`🔴 Live News`) before detection;
+    /// the title as written is kept in `original_title`
+    pub strip_leading_emoji: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            strip_prefix: None,
+            normalize_separators: false,
+            policy: CategorizationPolicy::default(),
+            max_attribute_length: DEFAULT_MAX_ATTRIBUTE_LENGTH,
+            max_items: None,
+            drop_separators: false,
+            normalize_urls: false,
+            encode_url_spaces: false,
+            base_url: None,
+            expand_season_ranges: false,
+            strip_leading_emoji: false,
+        }
+    }
+}
+
+/// Non-fatal issue found while parsing an entry
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParseWarning {
+    /// 1-based line of the #EXTINF entry
+    pub line: usize,
+    pub message: String,
+}
+
+/// Parsed items together with parse diagnostics
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ParseResult {
+    pub items: Vec<M3UItem>,
+    pub warnings: Vec<ParseWarning>,
+    /// Parsing stopped at `max_items` with entries left unread
+    pub truncated: bool,
+    /// Playlist name from a `#PLAYLIST:` line
+    pub playlist_title: Option<String>,
+    /// `#EXTINF` entries dropped because the playlist ended before their URL
+    pub skipped_missing_url: usize,
+}
 
 /// High-performance streaming M3U parser
 pub struct M3UParser<'a> {
     content: &'a str,
-    cursor: usize,
+    pub(crate) cursor: usize,
+    pub(crate) options: ParserOptions,
+    pipeline: Option<&'a DetectorPipeline>,
+    line: usize,
+    pub(crate) entry_line: usize,
+    pub(crate) warnings: Vec<ParseWarning>,
+    pub(crate) playlist_title: Option<String>,
+    pub(crate) skipped_missing_url: usize,
+    /// Group-title interner: items of one group share a single allocation
+    groups: HashSet<Arc<str>>,
+    base_url: Option<Url>,
 }
 
 impl<'a> M3UParser<'a> {
     pub fn new(content: &'a str) -> Self {
-        Self { content, cursor: 0 }
+        Self::with_options(content, ParserOptions::default())
+    }
+
+    pub fn with_options(content: &'a str, options: ParserOptions) -> Self {
+        let mut warnings = Vec::new();
+        let base_url = options.base_url.as_deref().and_then(|base| match Url::parse(base) {
+            Ok(url) => Some(url),
+            Err(e) => {
+                warnings.push(ParseWarning {
+                    line: 0,
+                    message: format!("Invalid base URL {}: {}", base, e),
+                });
+                None
+            }
+        });
+
+        Self {
+            content,
+            cursor: 0,
+            options,
+            pipeline: None,
+            line: 0,
+            entry_line: 0,
+            warnings,
+            playlist_title: None,
+            skipped_missing_url: 0,
+            groups: HashSet::new(),
+            base_url,
+        }
+    }
+
+    /// Use a custom detector pipeline instead of the built-in stages
+    pub fn with_pipeline(mut self, pipeline: &'a DetectorPipeline) -> Self {
+        self.pipeline = Some(pipeline);
+        self
     }
 
     /// Parse M3U content into items
-    pub fn parse(&self) -> Result<Vec<M3UItem>, String> {
-        let mut parser = Self::new(self.content);
+    pub fn parse(&self) -> Result<Vec<M3UItem>, ParseError> {
+        self.parse_detailed().map(|result| result.items)
+    }
+
+    /// Parse M3U content into items, also returning non-fatal warnings
+    pub fn parse_detailed(&self) -> Result<ParseResult, ParseError> {
+        let mut items = Vec::new();
+        let result = self.parse_into(&mut items, &mut HashSet::new())?;
+        Ok(ParseResult { items, ..result })
+    }
+
+    /// `parse_detailed` into recycled buffers: `items` is cleared and refilled,
+    /// `groups` is a group-title interner kept from earlier parses (pruned to
+    /// the groups still in use). The returned result has no items of its own.
+    ///
+    /// Both buffers are left untouched when the content is rejected.
+    pub(crate) fn parse_into(
+        &self,
+        items: &mut Vec<M3UItem>,
+        groups: &mut HashSet<Arc<str>>,
+    ) -> Result<ParseResult, ParseError> {
+        let mut parser = Self::with_options(self.content, self.options.clone());
+        parser.pipeline = self.pipeline;
 
         // Verify header
         if !parser.read_header()? {
-            return Err("Invalid M3U file: missing #EXTM3U header".to_string());
+            return Err(ParseError::missing_header());
         }
 
-        let mut items = Vec::new();
+        items.clear();
+        groups.retain(|group| Arc::strong_count(group) > 1);
+        parser.groups = std::mem::take(groups);
+        let mut truncated = false;
 
         // Parse entries
-        while let Some((metadata_line, url_line)) = parser.read_entry() {
-            if let Some(item) = parser.parse_entry(metadata_line, url_line) {
-                items.push(item);
+        while let Some((metadata_line, directives, url_line)) = parser.read_entry() {
+            if self.options.max_items.is_some_and(|max| items.len() >= max) {
+                truncated = true;
+                break;
+            }
+
+            if let Some(item) = parser.parse_entry(metadata_line, &directives, url_line) {
+                if self.options.expand_season_ranges {
+                    items.extend(expand_season_range(item));
+                } else {
+                    items.push(item);
+                }
+            }
+
+            // A season range may expand past the limit: keep only the seasons that fit
+            if let Some(max) = self.options.max_items.filter(|max| items.len() > *max) {
+                items.truncate(max);
+                truncated = true;
+                break;
             }
         }
 
-        Ok(items)
+        *groups = parser.groups;
+        Ok(ParseResult {
+            items: Vec::new(),
+            warnings: parser.warnings,
+            truncated,
+            playlist_title: parser.playlist_title,
+            skipped_missing_url: parser.skipped_missing_url,
+        })
     }
 
     /// Read and verify M3U header
-    fn read_header(&mut self) -> Result<bool, String> {
+    pub(crate) fn read_header(&mut self) -> Result<bool, ParseError> {
         if let Some(line) = self.read_line() {
-            Ok(line.trim().starts_with("#EXTM3U"))
+            Ok(strip_bom(line).trim().starts_with("#EXTM3U"))
         } else {
-            Err("Empty file".to_string())
+            Err(ParseError::empty_file())
         }
     }
 
-    /// Read next entry (metadata line + URL line)
-    fn read_entry(&mut self) -> Option<(&'a str, &'a str)> {
+    /// Record playlist-level `#PLAYLIST:` metadata, returning whether the line was one
+    pub(crate) fn read_playlist_directive(&mut self, line: &str) -> bool {
+        match line.strip_prefix("#PLAYLIST:") {
+            Some(name) => {
+                self.playlist_title = Some(name.trim().to_string());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Read next entry (metadata line + entry directives + URL line)
+    ///
+    /// `#EXTVLCOPT`/`#KODIPROP`/`#EXTALB` lines before or after the `#EXTINF`
+    /// line are collected as directives of the entry.
+    pub(crate) fn read_entry(&mut self) -> Option<(&'a str, Vec<&'a str>, &'a str)> {
+        let mut directives = Vec::new();
+
         // Skip empty lines and comments (except #EXTINF)
         let metadata = loop {
             let line = self.read_line()?;
             let trimmed = line.trim();
 
             if trimmed.starts_with("#EXTINF") {
+                self.entry_line = self.line;
                 break line;
             }
 
+            if is_entry_directive(trimmed) {
+                directives.push(trimmed);
+                continue;
+            }
+
+            if self.read_playlist_directive(trimmed) {
+                continue;
+            }
+
             // Skip other comments and empty lines
             if trimmed.is_empty() || (trimmed.starts_with('#') && !trimmed.starts_with("#EXTINF")) {
                 continue;
@@ -63,59 +263,246 @@ impl<'a> M3UParser<'a> {
 
         // Read URL line
         let url = loop {
-            let line = self.read_line()?;
+            let Some(line) = self.read_line() else {
+                self.skip_missing_url();
+                return None;
+            };
             let trimmed = line.trim();
 
+            if is_entry_directive(trimmed) {
+                directives.push(trimmed);
+                continue;
+            }
+
             if !trimmed.is_empty() && !trimmed.starts_with('#') {
                 break line;
             }
         };
 
-        Some((metadata, url))
+        Some((metadata, directives, url))
     }
 
     /// Parse single entry
-    fn parse_entry(&self, metadata: &'a str, url: &'a str) -> Option<M3UItem> {
-        let url = url.trim();
+    pub(crate) fn parse_entry(
+        &mut self,
+        metadata: &str,
+        directives: &[&str],
+        url: &str,
+    ) -> Option<M3UItem> {
+        let mut url = url.trim();
+        let mut original_url = None;
+
+        let encoded;
+        if self.options.encode_url_spaces {
+            if let Cow::Owned(fixed) = encode_url_spaces(url) {
+                original_url = Some(url.to_string());
+                encoded = fixed;
+                url = &encoded;
+            }
+        }
+
+        let resolved;
+        if let Some(base) = &self.base_url {
+            if let Err(url::ParseError::RelativeUrlWithoutBase) = Url::parse(url) {
+                match base.join(url) {
+                    Ok(absolute) => {
+                        resolved = String::from(absolute);
+                        url = &resolved;
+                    }
+                    Err(e) => self.warn(format!("Cannot resolve URL {}: {}", url, e)),
+                }
+            }
+        }
+
+        let repaired;
+        if self.options.normalize_urls {
+            match normalize_url(url) {
+                Ok(Cow::Owned(fixed)) => {
+                    original_url.get_or_insert_with(|| url.to_string());
+                    repaired = fixed;
+                    url = &repaired;
+                }
+                Ok(Cow::Borrowed(_)) => {}
+                Err(e) => self.warn(format!("Invalid URL: {}", e)),
+            }
+        }
 
         // Parse #EXTINF line
         // Format: #EXTINF:duration tvg-logo="..." group-title="..." ,Title
 
-        let comma_pos = metadata.rfind(',')?;
-        let raw_title = metadata[comma_pos + 1..].trim();
-        let attributes = &metadata[..comma_pos];
+        let mut unterminated = Vec::new();
+        let (pairs, title_comma) = tokenize_attributes(metadata, &mut unterminated);
+        let comma_pos = title_comma?;
+        let duration = parse_duration(&metadata[..comma_pos]);
 
-        let mut logo = None;
-        let mut group = String::new();
+        // Smart quotes, NBSP and zero-width characters break search and sorting
+        let unicode_normalized = normalize_unicode(&metadata[comma_pos + 1..]);
+        let mut raw_title = unicode_normalized.trim();
 
-        // Simple attribute parsing (can be optimized with proper parser)
-        if let Some(logo_start) = attributes.find("tvg-logo=\"") {
-            let logo_value_start = logo_start + 10;
-            if let Some(logo_end) = attributes[logo_value_start..].find('"') {
-                logo = Some(attributes[logo_value_start..logo_value_start + logo_end].to_string());
+        // Remove provider prefix noise before categorization
+        let mut original_title = None;
+        if let Some(prefix) = self.options.strip_prefix.as_deref() {
+            if let Some(stripped) = raw_title.strip_prefix(prefix) {
+                original_title = Some(raw_title.to_string());
+                raw_title = stripped.trim_start();
             }
         }
 
-        if let Some(group_start) = attributes.find("group-title=\"") {
-            let group_value_start = group_start + 13;
-            if let Some(group_end) = attributes[group_value_start..].find('"') {
-                group = attributes[group_value_start..group_value_start + group_end].to_string();
+        if self.options.strip_leading_emoji {
+            let stripped = strip_leading_symbols(raw_title);
+            if stripped.len() < raw_title.len() && !stripped.is_empty() {
+                original_title.get_or_insert_with(|| raw_title.to_string());
+                raw_title = stripped;
             }
         }
 
+        // Tokenize the attribute section once into the generic attribute map; logo and
+        // group move into their own fields so the interned group isn't copied per item
+        let mut attributes = self.attributes(pairs, unterminated);
+        let logo = attributes.remove("tvg-logo");
+        let group_title = attributes.remove("group-title").unwrap_or_default();
+        let group = self.intern_group(&group_title);
+        let artwork: HashMap<String, String> = ARTWORK_ATTRIBUTES
+            .iter()
+            .filter_map(|name| Some((name.to_string(), attributes.get(*name)?.clone())))
+            .filter(|(_, value)| !value.is_empty())
+            .collect();
+        let recordable = RECORD_ATTRIBUTES
+            .iter()
+            .find_map(|name| attributes.get(*name))
+            .and_then(|value| parse_flag(value));
+
+        if self.options.drop_separators && is_separator_entry(raw_title, url) {
+            self.warn(format!("Dropped separator entry \"{}\"", raw_title));
+            return None;
+        }
+
+        // Empty title: fall back to tvg-name, then to the URL filename
+        let tvg_name: String;
+        if raw_title.is_empty() {
+            tvg_name = attributes.get("tvg-name").cloned().unwrap_or_default();
+            raw_title = Some(tvg_name.trim())
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| title_from_url(url));
+        }
+
+        // Opt-in separator cleanup so dotted/underscored titles are detected
+        let normalized;
+        if self.options.normalize_separators {
+            normalized = normalize_separators(raw_title);
+            raw_title = &normalized;
+        }
+
         // Categorize and extract metadata (year, season, episode)
-        let categorized = categorize_item(raw_title, url);
+        let pipeline = match self.pipeline {
+            Some(pipeline) => pipeline,
+            None => default_pipeline(),
+        };
+        let policy = &self.options.policy;
+        let categorized = categorize_entry(raw_title, url, &group, duration, policy, pipeline);
+
+        // Player options: #EXTVLCOPT:key=value, #KODIPROP:key=value
+        let mut vlc_options = HashMap::new();
+        let mut kodi_props = HashMap::new();
+        let mut album = None;
+        for directive in directives {
+            if let Some(name) = directive.strip_prefix("#EXTALB:") {
+                album = Some(name.trim().to_string());
+                continue;
+            }
+
+            let (target, option) = if let Some(option) = directive.strip_prefix("#EXTVLCOPT:") {
+                (&mut vlc_options, option)
+            } else if let Some(option) = directive.strip_prefix("#KODIPROP:") {
+                (&mut kodi_props, option)
+            } else {
+                continue;
+            };
 
-        Some(M3UItem {
-            title: categorized.cleaned_title,
+            if let Some((key, value)) = option.split_once('=') {
+                target.insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+
+        let mut item = M3UItem {
             url: url.to_string(),
+            original_url,
             group,
             logo,
-            category: categorized.category,
-            year: categorized.year,
-            season: categorized.season,
-            episode: categorized.episode,
-        })
+            album,
+            vlc_options,
+            kodi_props,
+            artwork,
+            recordable,
+            attributes,
+            duration,
+            original_title,
+            source_title: raw_title.to_string(),
+            ..Default::default()
+        };
+        item.apply_categorized(categorized);
+
+        Some(item)
+    }
+
+    /// Collect tokenized `#EXTINF` attributes into a map (lowercased keys),
+    /// truncating values to `max_attribute_length`
+    fn attributes(
+        &mut self,
+        pairs: Vec<(&str, Cow<str>)>,
+        unterminated: Vec<&str>,
+    ) -> HashMap<String, String> {
+        let max = self.options.max_attribute_length;
+        let mut attributes = HashMap::new();
+
+        for (name, value) in pairs {
+            // Cut the borrowed value first so only the kept prefix is allocated
+            let mut kept: &str = &value;
+            if kept.len() > max {
+                let mut end = max;
+                while !kept.is_char_boundary(end) {
+                    end -= 1;
+                }
+                self.warn(format!(
+                    "{} value truncated from {} to {} bytes",
+                    name,
+                    kept.len(),
+                    end
+                ));
+                kept = &kept[..end];
+            }
+            attributes.insert(name.to_ascii_lowercase(), kept.to_string());
+        }
+
+        for name in unterminated {
+            self.warn(format!("Missing closing quote in {} value", name));
+        }
+
+        attributes
+    }
+
+    /// Shared storage for a group-title, allocated once per distinct name
+    fn intern_group(&mut self, name: &str) -> Arc<str> {
+        if let Some(group) = self.groups.get(name) {
+            return Arc::clone(group);
+        }
+        let group: Arc<str> = Arc::from(name);
+        self.groups.insert(Arc::clone(&group));
+        group
+    }
+
+    /// Count an entry whose URL line is missing at the end of the playlist
+    pub(crate) fn skip_missing_url(&mut self) {
+        self.skipped_missing_url += 1;
+        self.warn("Entry has no URL (playlist ends after #EXTINF)".to_string());
+    }
+
+    /// Record a warning for the current entry
+    fn warn(&mut self, message: String) {
+        self.warnings.push(ParseWarning {
+            line: self.entry_line,
+            message,
+        });
     }
 
     /// Read next line
@@ -124,11 +511,14 @@ impl<'a> M3UParser<'a> {
             return None;
         }
 
+        self.line += 1;
+
         let start = self.cursor;
-        let remaining = &self.content[start..];
+        let remaining = &self.content.as_bytes()[start..];
 
-        // Find line end
-        let end = if let Some(pos) = remaining.find('\n') {
+        // Find line end; `\n` is ASCII and never part of a multi-byte
+        // sequence, so both ends are always char boundaries
+        let end = if let Some(pos) = memchr::memchr(b'\n', remaining) {
             self.cursor = start + pos + 1;
             start + pos
         } else {
@@ -137,16 +527,207 @@ impl<'a> M3UParser<'a> {
             self.content.len()
         };
 
-        let line = &self.content[start..end];
+        debug_assert!(self.content.is_char_boundary(start) && self.content.is_char_boundary(end));
+        let line = self.content.get(start..end)?;
 
         // Handle \r\n
         Some(line.trim_end_matches('\r'))
     }
 }
 
+/// Drop a UTF-8 byte order mark (char-aware, never splits a sequence)
+pub(crate) fn strip_bom(line: &str) -> &str {
+    line.strip_prefix('\u{FEFF}').unwrap_or(line)
+}
+
+/// One item per season of a season-range entry (`S01-S03` -> seasons 1, 2, 3),
+/// or just the item itself
+pub(crate) fn expand_season_range(item: M3UItem) -> Vec<M3UItem> {
+    let (Some(first), Some(last)) = (item.season, item.season_end) else {
+        return vec![item];
+    };
+    (first..=last)
+        .map(|season| M3UItem {
+            season: Some(season),
+            season_end: None,
+            ..item.clone()
+        })
+        .collect()
+}
+
+/// Per-entry directive lines carried over to the item (player options, album)
+pub(crate) fn is_entry_directive(line: &str) -> bool {
+    line.starts_with("#EXTVLCOPT:") || line.starts_with("#KODIPROP:") || line.starts_with("#EXTALB:")
+}
+
+/// Image attributes collected into `M3UItem::artwork`
+const ARTWORK_ATTRIBUTES: [&str; 8] = [
+    "tvg-logo-small",
+    "poster",
+    "tvg-poster",
+    "backdrop",
+    "tvg-backdrop",
+    "fanart",
+    "cover",
+    "banner",
+];
+
+/// Attributes flagging DVR support, first present one wins
+const RECORD_ATTRIBUTES: [&str; 2] = ["tvg-rec", "recordable"];
+
+/// `1`/`0`/`true`/`false` attribute value (case-insensitive)
+fn parse_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" => Some(true),
+        "0" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// URLs providers use as placeholders for separator entries
+const DUMMY_URLS: [&str; 4] = ["http://dummy", "http://localhost", "http://0.0.0.0", "about:blank"];
+
+/// Visual section header (`###### MOVIES ######`, `----`) or placeholder URL
+fn is_separator_entry(title: &str, url: &str) -> bool {
+    let is_separator = |c: char| "#=-*~_|•★".contains(c);
+    let decorated = |run: &str| run.chars().take_while(|&c| is_separator(c)).count() >= 3;
+
+    let only_punctuation = !title.is_empty() && !title.chars().any(char::is_alphanumeric);
+    let banner = decorated(title) && decorated(&title.chars().rev().collect::<String>());
+    let url = url.trim_end_matches('/').to_ascii_lowercase();
+    let dummy_url = url.is_empty()
+        || DUMMY_URLS.contains(&url.as_str())
+        || url.contains("://dummy.")
+        || url.ends_with("/dummy");
+
+    only_punctuation || banner || dummy_url
+}
+
+/// Leading duration token of an `#EXTINF` line (`#EXTINF:-1 ...`, `#EXTINF:7200.5,`)
+fn parse_duration(section: &str) -> Option<f64> {
+    let section = section.strip_prefix("#EXTINF:").unwrap_or(section);
+    section.split_whitespace().next()?.parse().ok()
+}
+
+/// Split an `#EXTINF` line into `key=value` pairs, returning them with the
+/// byte offset of the comma that starts the title
+///
+/// Small state machine over the bytes after `#EXTINF:`: bare tokens (the
+/// duration) are skipped, values may be double-quoted, single-quoted or
+/// unquoted, whitespace around `=` is allowed and `\"`/`\\` escape a quote or
+/// a backslash. A value with a missing closing quote is read up to the next
+/// whitespace or comma and its key is pushed to `unterminated`. The first
+/// comma outside a quoted value ends the attributes, so titles may contain
+/// commas (`Movie, The`); `None` when the line has no such comma.
+pub(crate) fn tokenize_attributes<'a>(
+    line: &'a str,
+    unterminated: &mut Vec<&'a str>,
+) -> (Vec<(&'a str, Cow<'a, str>)>, Option<usize>) {
+    let section = line.strip_prefix("#EXTINF:").unwrap_or(line);
+    let offset = line.len() - section.len();
+    let bytes = section.as_bytes();
+    let len = bytes.len();
+    let is_boundary = |byte: u8| byte == b',' || byte.is_ascii_whitespace();
+    let mut pairs = Vec::new();
+    let mut i = 0;
+
+    while i < len {
+        // Key (or bare token): up to '=', whitespace or the title comma
+        while i < len && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if bytes.get(i) == Some(&b',') {
+            return (pairs, Some(offset + i));
+        }
+        let key_start = i;
+        while i < len && bytes[i] != b'=' && !is_boundary(bytes[i]) {
+            i += 1;
+        }
+        let key = &section[key_start..i];
+
+        let mut j = i;
+        while j < len && bytes[j].is_ascii_whitespace() {
+            j += 1;
+        }
+        if j >= len || bytes[j] != b'=' || key.is_empty() {
+            // Bare token such as the duration: skip it
+            if i == key_start {
+                i += 1;
+            }
+            continue;
+        }
+
+        // Value after '=' (optionally surrounded by whitespace)
+        i = j + 1;
+        while i < len && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        let value = match bytes.get(i) {
+            Some(&quote) if quote == b'"' || quote == b'\'' => {
+                let start = i + 1;
+                let mut end = start;
+                let mut escaped = false;
+                while end < len && bytes[end] != quote {
+                    let next = bytes.get(end + 1).copied();
+                    if bytes[end] == b'\\' && (next == Some(quote) || next == Some(b'\\')) {
+                        escaped = true;
+                        end += 1;
+                    }
+                    end += 1;
+                }
+                if end >= len {
+                    // No closing quote: recover the first word
+                    unterminated.push(key);
+                    end = start;
+                    while end < len && !is_boundary(bytes[end]) {
+                        end += 1;
+                    }
+                    i = end;
+                } else {
+                    i = end + 1;
+                }
+                let raw = &section[start..end];
+                if escaped {
+                    Cow::Owned(unescape_value(raw, quote as char))
+                } else {
+                    Cow::Borrowed(raw)
+                }
+            }
+            _ => {
+                let start = i;
+                while i < len && !is_boundary(bytes[i]) {
+                    i += 1;
+                }
+                Cow::Borrowed(&section[start..i])
+            }
+        };
+
+        pairs.push((key, value));
+    }
+
+    (pairs, None)
+}
+
+/// Undo `\<quote>` and `\\` escapes in a quoted attribute value
+fn unescape_value(raw: &str, quote: char) -> String {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        match chars.peek() {
+            Some(&next) if c == '\\' && (next == quote || next == '\\') => {
+                value.push(next);
+                chars.next();
+            }
+            _ => value.push(c),
+        }
+    }
+    value
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ErrorCode;
 
     #[test]
     fn test_header_parsing() {
@@ -160,6 +741,21 @@ mod tests {
         assert!(parser.parse().is_err());
     }
 
+    #[test]
+    fn test_error_codes() {
+        let error = M3UParser::new("Invalid\n").parse().unwrap_err();
+        assert_eq!(error.code, ErrorCode::MissingHeader);
+        assert_eq!(error.line, Some(1));
+
+        let error = M3UParser::new("").parse().unwrap_err();
+        assert_eq!(error.code, ErrorCode::EmptyFile);
+        assert_eq!(error.line, None);
+
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "EmptyFile");
+        assert_eq!(json["message"], "Empty file");
+    }
+
     #[test]
     fn test_entry_parsing() {
         let content = r#"#EXTM3U
@@ -171,7 +767,572 @@ http://example.com/movie.mkv
 
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].title, "Test Movie");
-        assert_eq!(items[0].group, "Movies");
+        assert_eq!(&*items[0].group, "Movies");
         assert_eq!(items[0].logo, Some("http://example.com/logo.png".to_string()));
     }
+
+    #[test]
+    fn test_strip_prefix() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 group-title="Movies",VIP | Test Movie
+http://example.com/movie.mkv
+#EXTINF:-1 group-title="Live",VIP | News Channel
+http://example.com/live/news
+#EXTINF:-1 group-title="Movies",Other Movie
+http://example.com/other.mkv
+"#;
+        let options = ParserOptions {
+            strip_prefix: Some("VIP |".to_string()),
+            ..Default::default()
+        };
+        let items = M3UParser::with_options(content, options).parse().unwrap();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].title, "Test Movie");
+        assert_eq!(items[0].original_title.as_deref(), Some("VIP | Test Movie"));
+        assert_eq!(items[1].title, "News Channel");
+        assert_eq!(items[1].original_title.as_deref(), Some("VIP | News Channel"));
+        assert_eq!(items[2].title, "Other Movie");
+        assert_eq!(items[2].original_title, None);
+    }
+
+    #[test]
+    fn test_empty_title_falls_back_to_tvg_name() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 tvg-name="BBC One" group-title="UK",
+http://example.com/live/bbc1
+"#;
+        let items = M3UParser::new(content).parse().unwrap();
+        assert_eq!(items[0].title, "BBC One");
+    }
+
+    #[test]
+    fn test_empty_title_falls_back_to_filename() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 group-title="Movies",
+http://example.com/vod/Some_Movie.mkv?token=abc
+"#;
+        let items = M3UParser::new(content).parse().unwrap();
+        assert_eq!(items[0].title, "Some_Movie");
+    }
+
+    #[test]
+    fn test_bare_episode_title_uses_group() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 group-title="Great Show",S01E05
+http://example.com/show/s01e05.mkv
+#EXTINF:-1,S01E06
+http://example.com/show/s01e06.mkv
+"#;
+        let items = M3UParser::new(content).parse().unwrap();
+        assert_eq!(items[0].title, "Great Show");
+        assert_eq!(items[0].season, Some(1));
+        assert_eq!(items[0].episode, Some(5));
+        assert_eq!(items[1].title, "S01E06");
+    }
+
+    #[test]
+    fn test_normalize_separators_option() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 group-title="Series",The.Show.S01E01
+http://example.com/show.mkv
+#EXTINF:-1 group-title="Movies",Some_Great_Movie_(2020)
+http://example.com/movie.mkv
+"#;
+        let options = ParserOptions {
+            normalize_separators: true,
+            ..Default::default()
+        };
+        let items = M3UParser::with_options(content, options).parse().unwrap();
+        assert_eq!(items[0].title, "The Show");
+        assert_eq!(items[0].season, Some(1));
+        assert_eq!(items[0].episode, Some(1));
+        assert_eq!(items[1].title, "Some Great Movie");
+        assert_eq!(items[1].year, Some(2020));
+    }
+
+    #[test]
+    fn test_long_attribute_truncated() {
+        let long_group = "G".repeat(100);
+        let content = format!(
+            "#EXTM3U\n#EXTINF:-1 group-title=\"{}\",Movie\nhttp://example.com/movie.mkv\n",
+            long_group
+        );
+        let options = ParserOptions {
+            max_attribute_length: 10,
+            ..Default::default()
+        };
+        let result = M3UParser::with_options(&content, options).parse_detailed().unwrap();
+
+        assert_eq!(&*result.items[0].group, "GGGGGGGGGG");
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].line, 2);
+        assert!(result.warnings[0].message.contains("group-title"));
+
+        let result = M3UParser::new(&content).parse_detailed().unwrap();
+        assert_eq!(&*result.items[0].group, long_group);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_playback_directives() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 group-title="Live",Channel
+#EXTVLCOPT:http-user-agent=Mozilla/5.0
+#EXTVLCOPT:http-referrer=http://example.com/
+#KODIPROP:inputstream.adaptive.license_type=clearkey
+http://example.com/live/channel
+#EXTINF:-1 group-title="Live",Plain Channel
+http://example.com/live/plain
+"#;
+        let items = M3UParser::new(content).parse().unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].vlc_options["http-user-agent"], "Mozilla/5.0");
+        assert_eq!(items[0].vlc_options["http-referrer"], "http://example.com/");
+        assert_eq!(items[0].kodi_props["inputstream.adaptive.license_type"], "clearkey");
+        assert!(items[1].vlc_options.is_empty());
+        assert!(items[1].kodi_props.is_empty());
+    }
+
+    #[test]
+    fn test_max_items() {
+        let mut content = String::from("#EXTM3U\n");
+        for i in 0..10 {
+            content.push_str(&format!("#EXTINF:-1,Movie {}\nhttp://example.com/{}.mkv\n", i, i));
+        }
+
+        let options = ParserOptions {
+            max_items: Some(5),
+            ..Default::default()
+        };
+        let result = M3UParser::with_options(&content, options).parse_detailed().unwrap();
+        assert_eq!(result.items.len(), 5);
+        assert_eq!(result.items[4].title, "Movie 4");
+        assert!(result.truncated);
+
+        let options = ParserOptions {
+            max_items: Some(10),
+            ..Default::default()
+        };
+        let result = M3UParser::with_options(&content, options).parse_detailed().unwrap();
+        assert_eq!(result.items.len(), 10);
+        assert!(!result.truncated);
+    }
+
+    #[test]
+    fn test_title_unicode_normalized() {
+        let content = "#EXTM3U\n#EXTINF:-1 group-title=\"Movies\",Great\u{00A0}Movie\u{200B} (2020)\nhttp://example.com/movie.mkv\n";
+        let items = M3UParser::new(content).parse().unwrap();
+        assert_eq!(items[0].title, "Great Movie");
+        assert_eq!(items[0].year, Some(2020));
+    }
+
+    #[test]
+    fn test_playlist_and_album_metadata() {
+        let content = r#"#EXTM3U
+#PLAYLIST:My Favorite Channels
+#EXTINF:-1,Song Title
+#EXTALB:Greatest Hits
+http://example.com/song.mp3
+#EXTINF:-1,Other
+http://example.com/other.mp3
+"#;
+        let result = M3UParser::new(content).parse_detailed().unwrap();
+        assert_eq!(result.playlist_title, Some("My Favorite Channels".to_string()));
+        assert_eq!(result.items[0].album, Some("Greatest Hits".to_string()));
+        assert_eq!(result.items[1].album, None);
+    }
+
+    #[test]
+    fn test_tokenize_attributes() {
+        let mut unterminated = Vec::new();
+        let (pairs, title_comma) = tokenize_attributes(
+            r#"#EXTINF:-1 tvg-id=abc.tr tvg-name='Kanal D' group-title = "Haber" tvg-logo="l"   "#,
+            &mut unterminated,
+        );
+        assert_eq!(title_comma, None);
+        let pairs: Vec<(&str, &str)> = pairs.iter().map(|(k, v)| (*k, v.as_ref())).collect();
+        assert_eq!(
+            pairs,
+            [
+                ("tvg-id", "abc.tr"),
+                ("tvg-name", "Kanal D"),
+                ("group-title", "Haber"),
+                ("tvg-logo", "l"),
+            ]
+        );
+
+        assert!(unterminated.is_empty());
+
+        let section = r#"#EXTINF:0 tvg-name="Say \"Hi\"" x="unterminated"#;
+        let (pairs, _) = tokenize_attributes(section, &mut unterminated);
+        assert_eq!(pairs[0].1, "Say \"Hi\"");
+        assert_eq!(pairs[1].1, "unterminated");
+        assert_eq!(unterminated, ["x"]);
+        assert!(tokenize_attributes("#EXTINF:-1", &mut unterminated).0.is_empty());
+
+        // The title starts after the first comma outside a quoted value
+        let line = r#"#EXTINF:-1 group-title="News, Sports" tvg-id=a,Movie, The (2019)"#;
+        let (pairs, title_comma) = tokenize_attributes(line, &mut unterminated);
+        assert_eq!(pairs[0].1, "News, Sports");
+        assert_eq!(pairs[1].1, "a");
+        assert_eq!(&line[title_comma.unwrap() + 1..], "Movie, The (2019)");
+    }
+
+    #[test]
+    fn test_title_with_commas() {
+        let content = "#EXTM3U\n\
+            #EXTINF:-1 group-title=\"Drama, Classics\",Movie, The (2019)\n\
+            http://example.com/a.mkv\n\
+            #EXTINF:-1,Show, Part 2 S01E03\nhttp://example.com/b.mkv\n";
+        let items = M3UParser::new(content).parse().unwrap();
+
+        assert_eq!(&*items[0].group, "Drama, Classics");
+        assert_eq!(items[0].title, "Movie, The");
+        assert_eq!(items[0].year, Some(2019));
+        assert_eq!(items[1].title, "Show, Part 2");
+        assert_eq!(items[1].episode, Some(3));
+    }
+
+    #[test]
+    fn test_unterminated_quote_recovers() {
+        let content = "#EXTM3U\n#EXTINF:-1 tvg-id=\"a\" group-title=\"Movies,Some Movie\n\
+            http://example.com/movie.mkv\n";
+        let result = M3UParser::new(content).parse_detailed().unwrap();
+
+        assert_eq!(&*result.items[0].group, "Movies");
+        assert_eq!(result.items[0].title, "Some Movie");
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].line, 2);
+        assert!(result.warnings[0].message.contains("group-title"));
+    }
+
+    #[test]
+    fn test_attribute_map_on_item() {
+        let content =
+            "#EXTM3U\n#EXTINF:-1 TVG-ID=news.tr group-title='News',Channel\nhttp://x/live/1\n";
+        let items = M3UParser::new(content).parse().unwrap();
+        assert_eq!(&*items[0].group, "News");
+        assert_eq!(items[0].attributes["tvg-id"], "news.tr");
+        assert_eq!(items[0].attributes.len(), 1);
+    }
+
+    #[test]
+    fn test_drop_separator_entries() {
+        let content = r#"#EXTM3U
+#EXTINF:-1,###### MOVIES ######
+http://example.com/separator.mkv
+#EXTINF:-1,Real Movie
+http://example.com/movie.mkv
+#EXTINF:-1,-----
+http://example.com/dash.mkv
+#EXTINF:-1,Placeholder
+http://dummy
+#EXTINF:-1,#1 Hits
+http://example.com/live/hits
+"#;
+        let options = ParserOptions {
+            drop_separators: true,
+            ..Default::default()
+        };
+        let result = M3UParser::with_options(content, options).parse_detailed().unwrap();
+
+        let titles: Vec<&str> = result.items.iter().map(|item| item.title.as_str()).collect();
+        assert_eq!(titles, ["Real Movie", "#1 Hits"]);
+        assert_eq!(result.warnings.len(), 3);
+        assert_eq!(result.warnings[0].line, 2);
+        assert!(result.warnings[0].message.contains("MOVIES"));
+
+        // Off by default
+        assert_eq!(M3UParser::new(content).parse().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_artwork_attributes() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 tvg-logo="http://img/logo.png" poster="http://img/poster.jpg" backdrop="http://img/bg.jpg",Movie
+http://example.com/movie.mkv
+"#;
+        let items = M3UParser::new(content).parse().unwrap();
+
+        assert_eq!(items[0].logo.as_deref(), Some("http://img/logo.png"));
+        assert_eq!(items[0].artwork.len(), 2);
+        assert_eq!(items[0].artwork["poster"], "http://img/poster.jpg");
+        assert_eq!(items[0].artwork["backdrop"], "http://img/bg.jpg");
+    }
+
+    #[test]
+    fn test_multibyte_before_line_breaks() {
+        let content = "\u{FEFF}#EXTM3U\n\
+            #EXTINF:-1 group-title=\"Müzik\",Şarkı ğ\n\
+            http://example.com/ş.mp3\r\n\
+            #EXTINF:-1,Euro €\r\n\
+            http://example.com/€\n\
+            #EXTINF:-1,Emoji 😀\n\
+            http://example.com/😀";
+        let items = M3UParser::new(content).parse().unwrap();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].title, "Şarkı ğ");
+        assert_eq!(&*items[0].group, "Müzik");
+        assert_eq!(items[0].url, "http://example.com/ş.mp3");
+        assert_eq!(items[1].title, "Euro €");
+        assert_eq!(items[1].url, "http://example.com/€");
+        assert_eq!(items[2].title, "Emoji 😀");
+        assert_eq!(items[2].url, "http://example.com/😀");
+    }
+
+    #[test]
+    fn test_recordable_attribute() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 tvg-rec="1",Recordable Channel
+http://example.com/live/1
+#EXTINF:-1 tvg-rec="false",Plain Channel
+http://example.com/live/2
+#EXTINF:-1,Unknown Channel
+http://example.com/live/3
+"#;
+        let items = M3UParser::new(content).parse().unwrap();
+
+        assert_eq!(items[0].recordable, Some(true));
+        assert_eq!(items[1].recordable, Some(false));
+        assert_eq!(items[2].recordable, None);
+    }
+
+    #[test]
+    fn test_normalize_urls_option() {
+        let content = "#EXTM3U\n\
+            #EXTINF:-1,Spaced\nhttp://example.com/my movie.mkv\n\
+            #EXTINF:-1,Relative\n//example.com/live/1\n\
+            #EXTINF:-1,Broken\nexample.com/live/2\n";
+        let options = ParserOptions {
+            normalize_urls: true,
+            ..Default::default()
+        };
+        let result = M3UParser::with_options(content, options).parse_detailed().unwrap();
+
+        assert_eq!(result.items[0].url, "http://example.com/my%20movie.mkv");
+        let original = result.items[0].original_url.as_deref();
+        assert_eq!(original, Some("http://example.com/my movie.mkv"));
+        assert_eq!(result.items[1].url, "http://example.com/live/1");
+        assert_eq!(result.items[2].url, "example.com/live/2");
+        assert_eq!(result.items[2].original_url, None);
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].line, 6);
+    }
+
+    #[test]
+    fn test_strip_leading_emoji() {
+        let content = "#EXTM3U\n\
+            #EXTINF:-1,🔴 Live News\nhttp://example.com/live/news\n\
+            #EXTINF:-1,⭐ VIP Movie (2020)\nhttp://example.com/vip.mkv\n\
+            #EXTINF:-1,Plain\nhttp://example.com/plain.mkv\n";
+        let options = ParserOptions {
+            strip_leading_emoji: true,
+            ..Default::default()
+        };
+        let items = M3UParser::with_options(content, options).parse().unwrap();
+
+        assert_eq!(items[0].title, "Live News");
+        assert_eq!(items[0].original_title.as_deref(), Some("🔴 Live News"));
+        assert_eq!(items[1].title, "VIP Movie");
+        assert_eq!(items[1].year, Some(2020));
+        assert_eq!(items[1].original_title.as_deref(), Some("⭐ VIP Movie (2020)"));
+        assert_eq!(items[2].original_title, None);
+
+        // Off by default
+        let items = M3UParser::new(content).parse().unwrap();
+        assert_eq!(items[0].title, "🔴 Live News");
+        assert_eq!(items[0].original_title, None);
+    }
+
+    #[test]
+    fn test_duration() {
+        let content = "#EXTM3U\n\
+            #EXTINF:7200 tvg-id=\"m\",Long Film\nhttp://example.com/vod/stream/42\n\
+            #EXTINF:-1,News\nhttp://example.com/stream/43\n\
+            #EXTINF:,Blank\nhttp://example.com/a.mkv\n";
+        let items = M3UParser::new(content).parse().unwrap();
+
+        assert_eq!(items[0].duration, Some(7200.0));
+        assert_eq!(items[0].category, crate::Category::Movie);
+        assert_eq!(items[1].duration, Some(-1.0));
+        assert_eq!(items[1].category, crate::Category::LiveStream);
+        assert_eq!(items[2].duration, None);
+    }
+
+    #[test]
+    fn test_expand_season_ranges() {
+        let content = "#EXTM3U\n\
+            #EXTINF:-1,Show Complete S01-S03\nhttp://example.com/show/pack.mkv\n\
+            #EXTINF:-1,Movie (2020)\nhttp://example.com/movie.mkv\n";
+        let options = ParserOptions {
+            expand_season_ranges: true,
+            ..Default::default()
+        };
+        let items = M3UParser::with_options(content, options).parse().unwrap();
+
+        assert_eq!(items.len(), 4);
+        for (season, item) in (1..=3).zip(&items) {
+            assert_eq!(item.title, "Show");
+            assert_eq!(item.category, crate::Category::Series);
+            assert_eq!(item.season, Some(season));
+            assert_eq!(item.season_end, None);
+            assert_eq!(item.url, "http://example.com/show/pack.mkv");
+        }
+        assert_eq!(items[3].title, "Movie");
+
+        // Off by default
+        let items = M3UParser::new(content).parse().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!((items[0].season, items[0].season_end), (Some(1), Some(3)));
+    }
+
+    #[test]
+    fn test_max_items_limits_expanded_seasons() {
+        let content = "#EXTM3U\n\
+            #EXTINF:-1,Movie (2020)\nhttp://example.com/movie.mkv\n\
+            #EXTINF:-1,Show Complete S01-S03\nhttp://example.com/show/pack.mkv\n";
+        let options = ParserOptions {
+            expand_season_ranges: true,
+            max_items: Some(3),
+            ..Default::default()
+        };
+        let result = M3UParser::with_options(content, options).parse_detailed().unwrap();
+
+        assert_eq!(result.items.len(), 3);
+        assert_eq!(result.items[2].season, Some(2));
+        assert!(result.truncated);
+
+        let options = ParserOptions {
+            expand_season_ranges: true,
+            max_items: Some(4),
+            ..Default::default()
+        };
+        let result = M3UParser::with_options(content, options).parse_detailed().unwrap();
+        assert_eq!(result.items.len(), 4);
+        assert!(!result.truncated);
+    }
+
+    #[test]
+    fn test_encode_url_spaces_option() {
+        let content = "#EXTM3U\n\
+            #EXTINF:-1,Trailing\nhttp://example.com/movie.mkv  \n\
+            #EXTINF:-1,Interior\nhttp://example.com/my movie.mkv\n\
+            #EXTINF:-1,Valid\nhttp://example.com/my%20movie.mkv\n";
+        let options = ParserOptions {
+            encode_url_spaces: true,
+            ..Default::default()
+        };
+        let items = M3UParser::with_options(content, options).parse().unwrap();
+
+        assert_eq!(items[0].url, "http://example.com/movie.mkv");
+        assert_eq!(items[0].original_url, None);
+        assert_eq!(items[1].url, "http://example.com/my%20movie.mkv");
+        assert_eq!(items[1].original_url.as_deref(), Some("http://example.com/my movie.mkv"));
+        assert_eq!(items[2].url, "http://example.com/my%20movie.mkv");
+        assert_eq!(items[2].original_url, None);
+
+        // Off by default: only trimmed
+        let items = M3UParser::new(content).parse().unwrap();
+        assert_eq!(items[0].url, "http://example.com/movie.mkv");
+        assert_eq!(items[1].url, "http://example.com/my movie.mkv");
+    }
+
+    #[test]
+    fn test_trailing_entry_without_url() {
+        let content =
+            "#EXTM3U\n#EXTINF:-1,Movie\nhttp://example.com/movie.mkv\n#EXTINF:-1,Dangling\n";
+        let result = M3UParser::new(content).parse_detailed().unwrap();
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.skipped_missing_url, 1);
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].line, 4);
+    }
+
+    #[test]
+    fn test_base_url_resolution() {
+        let content = "#EXTM3U\n\
+            #EXTINF:-1,Segment\nsegment.ts\n\
+            #EXTINF:-1,Sibling\n../other/live.m3u8\n\
+            #EXTINF:-1,Absolute\nhttps://cdn.example.com/movie.mkv\n";
+        let options = ParserOptions {
+            base_url: Some("http://example.com/lists/main.m3u".to_string()),
+            ..Default::default()
+        };
+        let result = M3UParser::with_options(content, options).parse_detailed().unwrap();
+
+        assert_eq!(result.items[0].url, "http://example.com/lists/segment.ts");
+        assert_eq!(result.items[1].url, "http://example.com/other/live.m3u8");
+        assert_eq!(result.items[2].url, "https://cdn.example.com/movie.mkv");
+        assert!(result.warnings.is_empty());
+
+        // Without a base relative URLs are kept as-is
+        let items = M3UParser::new(content).parse().unwrap();
+        assert_eq!(items[0].url, "segment.ts");
+    }
+
+    #[test]
+    fn test_keep_year_in_title() {
+        let content = "#EXTM3U\n\
+            #EXTINF:-1,Great Movie (2022)\nhttp://example.com/movie.mkv\n\
+            #EXTINF:-1,Other Movie 2019\nhttp://example.com/other.mkv\n\
+            #EXTINF:-1,2019 Movie\nhttp://example.com/leading.mkv\n\
+            #EXTINF:-1,No Year\nhttp://example.com/noyear.mkv\n";
+        let options = ParserOptions {
+            policy: CategorizationPolicy {
+                strip_year_from_title: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let items = M3UParser::with_options(content, options).parse().unwrap();
+
+        assert_eq!(items[0].title, "Great Movie (2022)");
+        assert_eq!(items[0].year, Some(2022));
+        // Left as written, not rewritten into `Title (year)`
+        assert_eq!(items[1].title, "Other Movie 2019");
+        assert_eq!(items[1].year, Some(2019));
+        assert_eq!(items[2].title, "2019 Movie");
+        assert_eq!(items[2].year, Some(2019));
+        assert_eq!(items[3].title, "No Year");
+
+        let items = M3UParser::new(content).parse().unwrap();
+        assert_eq!(items[0].title, "Great Movie");
+        assert_eq!(items[0].year, Some(2022));
+    }
+
+    #[test]
+    fn test_group_titles_are_interned() {
+        let mut content = String::from("#EXTM3U\n");
+        for i in 0..50 {
+            let group = if i % 2 == 0 { "Action" } else { "Drama" };
+            content.push_str(&format!(
+                "#EXTINF:-1 group-title=\"{}\",Movie {}\nhttp://example.com/{}.mkv\n",
+                group, i, i
+            ));
+        }
+        let items = M3UParser::new(&content).parse().unwrap();
+
+        assert!(Arc::ptr_eq(&items[0].group, &items[2].group));
+        assert!(!Arc::ptr_eq(&items[0].group, &items[1].group));
+        // One allocation per distinct group, shared by all 25 items
+        assert_eq!(Arc::strong_count(&items[0].group), 25);
+        assert_eq!(Arc::strong_count(&items[1].group), 25);
+    }
+
+    #[test]
+    fn test_attributes_skip_group_and_logo() {
+        let content = "#EXTM3U\n#EXTINF:-1 tvg-id=\"a\" tvg-logo=\"l.png\" group-title=\"Haber\",\
+            TRT\nhttp://example.com/live\n";
+        let items = M3UParser::new(content).parse().unwrap();
+
+        assert_eq!(&*items[0].group, "Haber");
+        assert_eq!(items[0].logo.as_deref(), Some("l.png"));
+        assert!(!items[0].attributes.contains_key("group-title"));
+        assert!(!items[0].attributes.contains_key("tvg-logo"));
+        assert_eq!(items[0].attributes.get("tvg-id").map(String::as_str), Some("a"));
+    }
 }
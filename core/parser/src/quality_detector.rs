@@ -0,0 +1,84 @@
+use regex::Regex;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    /// Regex pattern for bracketed quality tags
+    /// Matches: [HD], (FHD), [4K], (1080p), etc.
+    static ref QUALITY_PATTERN: Regex =
+        Regex::new(r"(?i)[(\[]\s*(sd|hd|fhd|uhd|4k|8k|720p|1080p|2160p)\s*[)\]]").unwrap();
+
+    /// Regex pattern for quality tokens, bracketed or bare
+    /// Matches: 1080p, 4K, [HD], (FHD), etc.
+    static ref QUALITY_TOKEN_PATTERN: Regex =
+        Regex::new(r"(?i)\b(sd|hd|fhd|uhd|4k|8k|480p|720p|1080p|2160p)\b").unwrap();
+}
+
+/// Result of quality tag detection
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityInfo {
+    pub quality: String,
+    pub cleaned_title: String,
+}
+
+/// Detect and extract a bracketed quality tag from title
+///
+/// Only bracketed tags are recognized, so words like "HD" inside a name stay.
+/// The tag is normalized (`[fhd]` -> "FHD", `(1080P)` -> "1080p").
+///
+/// Examples:
+/// - "Channel [HD]" -> QualityInfo { quality: "HD", cleaned_title: "Channel" }
+/// - "Movie (FHD)" -> QualityInfo { quality: "FHD", cleaned_title: "Movie" }
+pub fn detect_quality(title: &str) -> Option<QualityInfo> {
+    let captures = QUALITY_PATTERN.captures(title)?;
+    let full_match = captures.get(0)?;
+    let tag = captures.get(1)?.as_str();
+
+    let quality = if tag.ends_with(['p', 'P']) {
+        tag.to_lowercase()
+    } else {
+        tag.to_uppercase()
+    };
+
+    let mut cleaned = String::with_capacity(title.len());
+    cleaned.push_str(&title[..full_match.start()]);
+    cleaned.push(' ');
+    cleaned.push_str(&title[full_match.end()..]);
+
+    let cleaned_title = cleaned.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    Some(QualityInfo {
+        quality,
+        cleaned_title,
+    })
+}
+
+/// Whether the title carries a quality token anywhere, bracketed or bare
+///
+/// Looser than `detect_quality`, which only strips bracketed tags.
+pub(crate) fn has_quality_token(title: &str) -> bool {
+    QUALITY_TOKEN_PATTERN.is_match(title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bracketed_quality() {
+        let info = detect_quality("Channel [HD]").unwrap();
+        assert_eq!(info.quality, "HD");
+        assert_eq!(info.cleaned_title, "Channel");
+
+        let info = detect_quality("Movie (FHD)").unwrap();
+        assert_eq!(info.quality, "FHD");
+        assert_eq!(info.cleaned_title, "Movie");
+
+        assert_eq!(detect_quality("Movie [1080P] (2020)").unwrap().quality, "1080p");
+    }
+
+    #[test]
+    fn test_unbracketed_quality_kept() {
+        assert!(detect_quality("News Channel HD").is_none());
+        assert!(detect_quality("HDTV Classics").is_none());
+    }
+}
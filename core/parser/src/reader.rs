@@ -0,0 +1,180 @@
+use std::io::BufRead;
+use crate::parser::{expand_season_range, is_entry_directive, strip_bom};
+use crate::{ErrorCode, M3UItem, M3UParser, ParseError, ParseWarning, ParserOptions};
+
+/// Streaming iterator over items read line by line from a `BufRead` source
+///
+/// Native analogue of `M3UParser::parse` for files that shouldn't be loaded
+/// into a single `String`. Entry handling is shared with `M3UParser`.
+pub struct ReaderItems<R: BufRead> {
+    reader: R,
+    parser: M3UParser<'static>,
+    line: String,
+    line_number: usize,
+    /// Items of an expanded season range not yet returned
+    pending: std::vec::IntoIter<M3UItem>,
+}
+
+/// Parse M3U content from a reader with default options
+pub fn parse_reader<R: BufRead>(reader: R) -> Result<ReaderItems<R>, ParseError> {
+    parse_reader_with_options(reader, ParserOptions::default())
+}
+
+/// Parse M3U content from a reader, verifying the `#EXTM3U` header up front
+pub fn parse_reader_with_options<R: BufRead>(
+    reader: R,
+    options: ParserOptions,
+) -> Result<ReaderItems<R>, ParseError> {
+    let mut items = ReaderItems {
+        reader,
+        parser: M3UParser::with_options("", options),
+        line: String::new(),
+        line_number: 0,
+        pending: Vec::new().into_iter(),
+    };
+
+    match items.next_line()? {
+        Some(line) if strip_bom(&line).trim().starts_with("#EXTM3U") => Ok(items),
+        Some(_) => Err(ParseError::missing_header()),
+        None => Err(ParseError::empty_file()),
+    }
+}
+
+impl<R: BufRead> ReaderItems<R> {
+    /// Warnings collected from the entries read so far
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.parser.warnings
+    }
+
+    /// Playlist name from a `#PLAYLIST:` line read so far
+    pub fn playlist_title(&self) -> Option<&str> {
+        self.parser.playlist_title.as_deref()
+    }
+
+    /// `#EXTINF` entries dropped because the input ended before their URL
+    pub fn skipped_missing_url(&self) -> usize {
+        self.parser.skipped_missing_url
+    }
+
+    /// Read next line without its line ending
+    fn next_line(&mut self) -> Result<Option<String>, ParseError> {
+        self.line.clear();
+        let read = self
+            .reader
+            .read_line(&mut self.line)
+            .map_err(|e| {
+                ParseError::new(ErrorCode::Io, format!("Read error: {}", e))
+                    .at_line(self.line_number + 1)
+            })?;
+
+        if read == 0 {
+            return Ok(None);
+        }
+        self.line_number += 1;
+
+        Ok(Some(self.line.trim_end_matches(['\n', '\r']).to_string()))
+    }
+
+    /// Read next entry (metadata line + entry directives + URL line),
+    /// mirroring `M3UParser::read_entry`
+    fn next_entry(&mut self) -> Result<Option<(String, Vec<String>, String)>, ParseError> {
+        let mut directives = Vec::new();
+
+        let metadata = loop {
+            match self.next_line()? {
+                Some(line) if line.trim().starts_with("#EXTINF") => {
+                    self.parser.entry_line = self.line_number;
+                    break line;
+                }
+                Some(line) if is_entry_directive(line.trim()) => {
+                    directives.push(line.trim().to_string());
+                }
+                Some(line) => {
+                    self.parser.read_playlist_directive(line.trim());
+                }
+                None => return Ok(None),
+            }
+        };
+
+        let url = loop {
+            match self.next_line()? {
+                Some(line) => {
+                    let trimmed = line.trim();
+                    if is_entry_directive(trimmed) {
+                        directives.push(trimmed.to_string());
+                    } else if !trimmed.is_empty() && !trimmed.starts_with('#') {
+                        break line;
+                    }
+                }
+                None => {
+                    self.parser.skip_missing_url();
+                    return Ok(None);
+                }
+            }
+        };
+
+        Ok(Some((metadata, directives, url)))
+    }
+}
+
+impl<R: BufRead> Iterator for ReaderItems<R> {
+    type Item = Result<M3UItem, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.pending.next() {
+            return Some(Ok(item));
+        }
+
+        loop {
+            match self.next_entry() {
+                Ok(Some((metadata, directives, url))) => {
+                    let directives: Vec<&str> = directives.iter().map(String::as_str).collect();
+                    let Some(item) = self.parser.parse_entry(&metadata, &directives, &url) else {
+                        continue;
+                    };
+                    if !self.parser.options.expand_season_ranges {
+                        return Some(Ok(item));
+                    }
+                    self.pending = expand_season_range(item).into_iter();
+                    return self.pending.next().map(Ok);
+                }
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_parse_from_cursor() {
+        let content = "#EXTM3U\r\n\
+#EXTINF:-1 group-title=\"Movies\",Great Movie (2022)\r\n\
+http://example.com/movie.mkv\r\n\
+\r\n\
+#EXTINF:-1 group-title=\"Series\",Show S01E02\r\n\
+http://example.com/show.mkv\r\n";
+
+        let items: Vec<M3UItem> = parse_reader(Cursor::new(content))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title, "Great Movie");
+        assert_eq!(items[0].year, Some(2022));
+        assert_eq!(items[1].title, "Show");
+        assert_eq!(items[1].episode, Some(2));
+        assert_eq!(items, M3UParser::new(content).parse().unwrap());
+    }
+
+    #[test]
+    fn test_reader_invalid_header() {
+        assert!(parse_reader(Cursor::new("Invalid\n")).is_err());
+        assert!(parse_reader(Cursor::new("")).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use crate::M3UItem;
+
+/// Serialize items back into a valid `#EXTM3U` playlist
+///
+/// Titles are written from `source_title` so re-parsing yields the same
+/// metadata (year, episode, ...). Player directives are written in key order.
+pub fn to_m3u<'a>(items: impl IntoIterator<Item = &'a M3UItem>) -> String {
+    let mut output = String::from("#EXTM3U\n");
+    for item in items {
+        write_entry(&mut output, item);
+    }
+    output
+}
+
+/// Append a single `#EXTINF` entry (metadata, directives, URL)
+fn write_entry(output: &mut String, item: &M3UItem) {
+    output.push_str(&format!("#EXTINF:{}", item.duration.unwrap_or(-1.0)));
+    if let Some(logo) = &item.logo {
+        output.push_str(&format!(" tvg-logo=\"{}\"", escape_attribute(logo)));
+    }
+    if !item.group.is_empty() {
+        output.push_str(&format!(" group-title=\"{}\"", escape_attribute(&item.group)));
+    }
+    let mut names: Vec<&String> = item
+        .attributes
+        .keys()
+        .filter(|name| *name != "tvg-logo" && *name != "group-title")
+        .collect();
+    names.sort();
+    for name in names {
+        output.push_str(&format!(" {}=\"{}\"", name, escape_attribute(&item.attributes[name])));
+    }
+
+    let title = if item.source_title.is_empty() {
+        &item.title
+    } else {
+        &item.source_title
+    };
+    output.push(',');
+    output.push_str(title);
+    output.push('\n');
+
+    if let Some(album) = &item.album {
+        output.push_str(&format!("#EXTALB:{}\n", album));
+    }
+    write_directives(output, "#EXTVLCOPT:", &item.vlc_options);
+    write_directives(output, "#KODIPROP:", &item.kodi_props);
+
+    output.push_str(&item.url);
+    output.push('\n');
+}
+
+/// Escape `\` and `"` so a value survives being written inside double quotes
+fn escape_attribute(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Append `prefix` + `key=value` lines in key order
+fn write_directives(output: &mut String, prefix: &str, options: &HashMap<String, String>) {
+    let mut keys: Vec<&String> = options.keys().collect();
+    keys.sort();
+    for key in keys {
+        output.push_str(&format!("{}{}={}\n", prefix, key, options[key]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::M3UParser;
+
+    #[test]
+    fn test_round_trip() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 tvg-logo="http://example.com/logo.png" group-title="Movies",Great Movie (2022)
+http://example.com/movie.mkv
+#EXTINF:-1 tvg-id="show.tr" group-title="Series",Show S01E02
+#EXTVLCOPT:http-user-agent=ZenithTV
+http://example.com/show.mkv
+#EXTINF:-1,Live Channel
+http://example.com/live/channel
+"#;
+        let items = M3UParser::new(content).parse().unwrap();
+        let output = to_m3u(&items);
+
+        assert!(output.starts_with("#EXTM3U\n"));
+        assert_eq!(M3UParser::new(&output).parse().unwrap(), items);
+    }
+
+    #[test]
+    fn test_round_trip_escapes_quotes() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 tvg-logo="C:\\logos\\a.png" group-title="The \"Best\" Movies" tvg-name="A \\ B",Movie
+http://example.com/movie.mkv
+"#;
+        let items = M3UParser::new(content).parse().unwrap();
+        assert_eq!(&*items[0].group, "The \"Best\" Movies");
+        assert_eq!(items[0].logo.as_deref(), Some("C:\\logos\\a.png"));
+        assert_eq!(items[0].attributes["tvg-name"], "A \\ B");
+
+        let output = to_m3u(&items);
+        assert!(output.contains(r#"group-title="The \"Best\" Movies""#));
+        assert_eq!(M3UParser::new(&output).parse().unwrap(), items);
+    }
+
+    #[test]
+    fn test_round_trip_title_with_commas() {
+        let content = r#"#EXTM3U
+#EXTINF:-1 group-title="Drama, Classics",Movie, The (2019)
+http://example.com/movie.mkv
+#EXTINF:-1,Hello, World, Again
+http://example.com/hello.mkv
+"#;
+        let items = M3UParser::new(content).parse().unwrap();
+        assert_eq!(items[0].source_title, "Movie, The (2019)");
+        assert_eq!(items[1].title, "Hello, World, Again");
+
+        let output = to_m3u(&items);
+        assert_eq!(M3UParser::new(&output).parse().unwrap(), items);
+    }
+}
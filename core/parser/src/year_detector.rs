@@ -1,5 +1,6 @@
 use regex::Regex;
 use lazy_static::lazy_static;
+use crate::episode_detector::has_digit;
 
 lazy_static! {
     /// Regex pattern for year detection (1900-2099)
@@ -24,6 +25,11 @@ pub struct YearInfo {
 /// - "Show 2023 Episode" -> YearInfo { year: 2023, cleaned_title: "Show Episode" }
 /// - "Old Film [1999]" -> YearInfo { year: 1999, cleaned_title: "Old Film" }
 pub fn detect_year(title: &str) -> Option<YearInfo> {
+    // Fast path: no digits means no year, skip the regex
+    if !has_digit(title) {
+        return None;
+    }
+
     let captures = YEAR_PATTERN.find(title)?;
     let year_str = captures.as_str();
     let year: u32 = year_str.parse().ok()?;
@@ -67,7 +73,6 @@ pub fn detect_year(title: &str) -> Option<YearInfo> {
 
     // Trim whitespace and clean up double spaces
     let cleaned_title = cleaned
-        .trim()
         .split_whitespace()
         .collect::<Vec<&str>>()
         .join(" ");
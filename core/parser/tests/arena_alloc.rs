@@ -0,0 +1,65 @@
+//! Allocation counting for `ParserArena`, kept in its own test binary so the
+//! counting `#[global_allocator]` doesn't wrap the library's unit tests
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use zenith_parser::{M3UParser, ParserArena, ParserOptions};
+
+/// Counts allocations made by the current thread, so harness threads are ignored
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn test_arena_reuses_buffers() {
+    let mut content = String::from("#EXTM3U\n");
+    for i in 0..500 {
+        content.push_str(&format!(
+            "#EXTINF:-1 group-title=\"Movies\",Movie {}\nhttp://example.com/{}.mkv\n",
+            i, i
+        ));
+    }
+
+    // Warm up lazily compiled regexes and their per-thread caches
+    let expected = M3UParser::new(&content).parse().unwrap();
+
+    let fresh = count_allocations(|| {
+        M3UParser::new(&content).parse().unwrap();
+    });
+
+    let mut arena = ParserArena::new();
+    arena.parse(&content, ParserOptions::default()).unwrap();
+    let reused = count_allocations(|| {
+        arena.parse(&content, ParserOptions::default()).unwrap();
+    });
+
+    assert_eq!(arena.result().items, expected);
+    assert!(reused < fresh, "reused {} >= fresh {}", reused, fresh);
+}